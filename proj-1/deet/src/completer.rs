@@ -0,0 +1,128 @@
+use rustyline::completion::Completer;
+use rustyline::highlight::Highlighter;
+use rustyline::hint::Hinter;
+use rustyline::validate::Validator;
+use rustyline::{Context, Helper};
+
+/// Command names offered when completing the first word of a line.
+const COMMANDS: &[&str] = &[
+    "backtrace",
+    "break",
+    "continue",
+    "info",
+    "ptype",
+    "quit",
+    "run",
+    "set",
+    "stack",
+    "step",
+    "trace",
+    "untrace",
+    "whatis",
+];
+
+/// Commands whose argument is a function name.
+const FUNCTION_COMMANDS: &[&str] = &["b", "break", "trace", "untrace", "whatis", "ptype"];
+
+/// Returns the position where the word under the cursor starts, along with the sorted candidates
+/// that could replace it: command names for the first word, or function names for the argument
+/// of a command that takes one.
+pub fn complete_line(line: &str, pos: usize, function_names: &[String]) -> (usize, Vec<String>) {
+    let line = &line[..pos];
+    let start = line.rfind(char::is_whitespace).map(|i| i + 1).unwrap_or(0);
+    let word = &line[start..];
+    let preceding: Vec<&str> = line[..start].split_whitespace().collect();
+
+    let mut candidates: Vec<String> = match preceding.as_slice() {
+        [] => COMMANDS.iter().map(|cmd| cmd.to_string()).collect(),
+        [cmd] if FUNCTION_COMMANDS.contains(cmd) => function_names.to_vec(),
+        _ => Vec::new(),
+    };
+    candidates.retain(|candidate| candidate.starts_with(word));
+    candidates.sort();
+    candidates.dedup();
+    (start, candidates)
+}
+
+/// Readline helper that tab-completes command and function names.
+pub struct DeetHelper {
+    function_names: Vec<String>,
+}
+
+impl DeetHelper {
+    pub fn new(function_names: Vec<String>) -> DeetHelper {
+        DeetHelper { function_names }
+    }
+}
+
+impl Completer for DeetHelper {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        Ok(complete_line(line, pos, &self.function_names))
+    }
+}
+
+impl Hinter for DeetHelper {}
+
+impl Highlighter for DeetHelper {}
+
+impl Validator for DeetHelper {}
+
+impl Helper for DeetHelper {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn function_names() -> Vec<String> {
+        vec!["func1".to_string(), "func2".to_string(), "main".to_string()]
+    }
+
+    #[test]
+    fn test_complete_command_prefix() {
+        assert_eq!(complete_line("br", 2, &[]), (0, vec!["break".to_string()]));
+        assert_eq!(
+            complete_line("  s", 3, &[]),
+            (
+                2,
+                vec!["set".to_string(), "stack".to_string(), "step".to_string()]
+            )
+        );
+        assert_eq!(complete_line("xyz", 3, &[]), (0, Vec::new()));
+    }
+
+    #[test]
+    fn test_complete_function_after_break() {
+        let names = function_names();
+        assert_eq!(
+            complete_line("break func", 10, &names),
+            (6, vec!["func1".to_string(), "func2".to_string()])
+        );
+        assert_eq!(complete_line("b m", 3, &names), (2, vec!["main".to_string()]));
+        assert_eq!(complete_line("b ", 2, &names), (2, names.clone()));
+    }
+
+    #[test]
+    fn test_no_candidates_after_argumentless_command() {
+        let names = function_names();
+        assert_eq!(complete_line("cont ", 5, &names), (5, Vec::new()));
+        assert_eq!(complete_line("quit m", 6, &names), (5, Vec::new()));
+        // Only the first argument of break is a function name.
+        assert_eq!(complete_line("break main f", 12, &names), (11, Vec::new()));
+    }
+
+    #[test]
+    fn test_start_offset_uses_cursor_position() {
+        let names = function_names();
+        assert_eq!(
+            complete_line("trace fu rest", 8, &names),
+            (6, vec!["func1".to_string(), "func2".to_string()])
+        );
+    }
+}
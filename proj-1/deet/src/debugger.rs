@@ -1,17 +1,36 @@
+use crate::completer::DeetHelper;
 use crate::debugger_command::DebuggerCommand;
 use crate::dwarf_data::{DwarfData, Error as DwarfError};
 use crate::inferior::Inferior;
 use crate::inferior::Status;
+use crate::inferior::{register_values, ARGUMENT_REGISTERS};
+use libc::user_regs_struct;
+use nix::sys::signal::Signal;
 use rustyline::error::ReadlineError;
 use rustyline::Editor;
+use std::collections::HashMap;
+use std::time::Instant;
+
+/// Number of words printed by the stack command when no count is given.
+const DEFAULT_STACK_WORDS: usize = 16;
 
 pub struct Debugger {
     target: String,
     history_path: String,
-    readline: Editor<()>,
+    readline: Editor<DeetHelper>,
     inferior: Option<Inferior>,
     debug_data: DwarfData,
     breakpoints: Vec<usize>,
+    // Maps the address of each traced function to its name
+    traces: HashMap<usize, String>,
+    track_registers: bool,
+    last_registers: Option<user_regs_struct>,
+    // When the inferior was last resumed, used to report how long it ran before stopping
+    resumed_at: Option<Instant>,
+    // The signal that caused the inferior's most recent stop
+    stop_signal: Option<Signal>,
+    // Whether step stops inside code inlined from other functions
+    step_inlined: bool,
 }
 
 fn parse_address(addr: &str, debug_data: &DwarfData) -> Option<usize> {
@@ -28,6 +47,26 @@ fn parse_address(addr: &str, debug_data: &DwarfData) -> Option<usize> {
     debug_data.get_addr_for_function(None, addr)
 }
 
+/// Parses a signal name such as "SIGUSR1" or "usr1".
+fn parse_signal(name: &str) -> Option<Signal> {
+    let name = name.to_uppercase();
+    if name.starts_with("SIG") {
+        name.parse().ok()
+    } else {
+        format!("SIG{}", name).parse().ok()
+    }
+}
+
+/// Interprets a raw register value as a signed integer of the given size in bytes.
+fn format_register_value(raw: u64, size: usize) -> String {
+    match size {
+        1 => format!("{}", raw as i8),
+        2 => format!("{}", raw as i16),
+        4 => format!("{}", raw as i32),
+        _ => format!("{}", raw as i64),
+    }
+}
+
 impl Debugger {
     /// Initializes the debugger.
     pub fn new(target: &str) -> Debugger {
@@ -45,7 +84,8 @@ impl Debugger {
         debug_data.print();
 
         let history_path = format!("{}/.deet_history", std::env::var("HOME").unwrap());
-        let mut readline = Editor::<()>::new();
+        let mut readline = Editor::<DeetHelper>::new();
+        readline.set_helper(Some(DeetHelper::new(debug_data.get_function_names())));
         // Attempt to load history from ~/.deet_history if it exists
         let _ = readline.load_history(&history_path);
 
@@ -56,23 +96,218 @@ impl Debugger {
             inferior: None,
             debug_data,
             breakpoints: Vec::new(),
+            traces: HashMap::new(),
+            track_registers: false,
+            last_registers: None,
+            resumed_at: None,
+            stop_signal: None,
+            step_inlined: true,
+        }
+    }
+
+    /// Prints the registers that changed since the previous stop, and remembers the current
+    /// register state for the next one.
+    fn print_changed_registers(&mut self) {
+        let regs = match self.inferior.as_ref().map(|inf| inf.get_registers()) {
+            Some(Ok(regs)) => regs,
+            _ => return,
+        };
+        if let Some(last) = &self.last_registers {
+            let changed: Vec<String> = register_values(last)
+                .iter()
+                .zip(register_values(&regs).iter())
+                .filter(|(old, new)| old.1 != new.1)
+                .map(|(old, new)| format!("  {}: {:#x} -> {:#x}", old.0, old.1, new.1))
+                .collect();
+            if changed.is_empty() {
+                println!("No registers changed");
+            } else {
+                println!("Changed registers:");
+                for line in changed {
+                    println!("{}", line);
+                }
+            }
+        }
+        self.last_registers = Some(regs);
+    }
+
+    /// Prints a call to a traced function along with its arguments. This must be called while the
+    /// inferior is stopped on the first instruction of the function, before the arguments have been
+    /// moved out of their registers.
+    ///
+    /// Only integer and pointer arguments are decoded. Floating-point arguments are passed in xmm
+    /// registers, so they don't use up a general-purpose one. We can't tell how many registers an
+    /// argument of unknown type takes (a struct can take none, one, or two), so neither it nor any
+    /// integer argument after it is shown.
+    fn print_trace(&self, func_name: &str) {
+        let regs = match self.inferior.as_ref().map(|inf| inf.get_registers()) {
+            Some(Ok(regs)) => regs,
+            _ => return,
+        };
+        let values = register_values(&regs);
+        let params = self
+            .debug_data
+            .get_function_parameters(func_name)
+            .unwrap_or_default();
+        let mut registers = ARGUMENT_REGISTERS.iter();
+        let mut lost_track = false;
+        let args: Vec<String> = params
+            .iter()
+            .map(|param| {
+                let value = if param.entity_type.is_float {
+                    "<float>".to_string()
+                } else if param.entity_type.is_unknown() {
+                    lost_track = true;
+                    "<unknown type>".to_string()
+                } else if lost_track {
+                    "<unknown register>".to_string()
+                } else {
+                    match registers.next() {
+                        // Arguments after the sixth are passed on the stack.
+                        None => "<on stack>".to_string(),
+                        Some(reg) => {
+                            let raw = values.iter().find(|(name, _)| name == reg).unwrap().1;
+                            if param.entity_type.is_pointer {
+                                format!("{:#x}", raw)
+                            } else {
+                                format_register_value(raw, param.entity_type.size)
+                            }
+                        }
+                    }
+                };
+                format!("{}={}", param.name, value)
+            })
+            .collect();
+        println!("trace: {}({})", func_name, args.join(", "));
+    }
+
+    /// Returns the name of the function the inferior is currently stopped in.
+    fn current_function(&self) -> Option<String> {
+        let regs = self.inferior.as_ref()?.get_registers().ok()?;
+        self.debug_data.get_function_from_addr(regs.rip as usize)
+    }
+
+    /// Prints num_words words of the inferior's stack starting at rsp, marking the saved rbp and
+    /// return address of each frame found while unwinding.
+    fn print_stack(&self, num_words: usize) {
+        let inferior = match &self.inferior {
+            Some(inf) => inf,
+            None => {
+                println!("No child process under debugging");
+                return;
+            }
+        };
+        let rsp = match inferior.get_registers() {
+            Ok(regs) => regs.rsp as usize,
+            Err(err) => {
+                println!("Failed to read registers. {}", err);
+                return;
+            }
+        };
+        // Frames we can't unwind just go unannotated.
+        let frames = inferior
+            .get_frame_slots(&self.debug_data)
+            .unwrap_or_default();
+
+        for i in 0..num_words {
+            let addr = rsp + i * std::mem::size_of::<usize>();
+            let word = match inferior.read_word(addr) {
+                Ok(word) => word,
+                Err(err) => {
+                    println!("Failed to read memory at {:#x}. {}", addr, err);
+                    return;
+                }
+            };
+            let mut annotation = String::new();
+            if let Some(frame) = frames.iter().position(|f| f.saved_rbp == Some(addr)) {
+                annotation = format!("  <- saved rbp (frame #{})", frame);
+            } else if let Some(frame) = frames.iter().position(|f| f.return_address == addr) {
+                annotation = format!("  <- return address (frame #{})", frame);
+            }
+            println!("{:#x}: {:#018x}{}", addr, word, annotation);
+        }
+    }
+
+    /// Continues the inferior until it stops somewhere other than a traced function, printing each
+    /// traced call along the way. If skip_signal is given, the first n stops caused by that signal
+    /// are passed on to the inferior without stopping. If the inferior is already stopped on that
+    /// signal, that stop counts as the first occurrence.
+    fn cont_inferior(&mut self, skip_signal: Option<(Signal, usize)>) {
+        self.resumed_at = Some(Instant::now());
+        let mut signal_count = 0;
+        let mut deliver: Option<Signal> = None;
+        if let Some((skip, _)) = skip_signal {
+            if self.stop_signal == Some(skip) {
+                signal_count = 1;
+                deliver = Some(skip);
+            }
+        }
+        loop {
+            let result = self.inferior.as_mut().unwrap().cont(deliver.take());
+            if let Ok(Status::Stopped(Signal::SIGTRAP, rip)) = &result {
+                if let Some(func_name) = self.traces.get(&(rip - 1)).cloned() {
+                    self.print_trace(&func_name);
+                    // Only stop here if the user also asked for a breakpoint.
+                    if !self.breakpoints.contains(&(rip - 1)) {
+                        continue;
+                    }
+                }
+            }
+            if let (Ok(Status::Stopped(signal, _)), Some((skip, num_to_skip))) =
+                (&result, skip_signal)
+            {
+                if *signal == skip {
+                    signal_count += 1;
+                    if signal_count <= num_to_skip {
+                        deliver = Some(skip);
+                        continue;
+                    }
+                    println!("Reached occurrence #{} of {}", signal_count, skip);
+                }
+            }
+            if let Some((skip, num_to_skip)) = skip_signal {
+                if let Ok(Status::Exited(_)) | Ok(Status::Signaled(_)) = &result {
+                    println!(
+                        "Child ended after {} occurrence(s) of {}, before reaching occurrence #{}",
+                        signal_count,
+                        skip,
+                        num_to_skip + 1
+                    );
+                }
+            }
+            self.print_inferior_run_result(result);
+            return;
         }
     }
 
-    fn print_inferior_run_result(&self, result: Result<Status, nix::Error>) {
+    fn print_inferior_run_result(&mut self, result: Result<Status, nix::Error>) {
         match result {
             Ok(status) => {
                 match status {
                     Status::Stopped(signal, rip) => {
+                        self.stop_signal = Some(signal);
                         println!("Child stopped (signal {})", signal);
+                        if let Some(resumed_at) = self.resumed_at.take() {
+                            println!(
+                                "Ran for {:.3}s since the last stop",
+                                resumed_at.elapsed().as_secs_f64()
+                            );
+                        }
                         if let Some(line) = &self.debug_data.get_line_from_addr(rip) {
                             println!("Stopped at {}:{}", line.file, line.number);
                         }
+                        if self.track_registers {
+                            self.print_changed_registers();
+                        }
                     }
                     Status::Exited(code) => {
+                        self.stop_signal = None;
                         println!("Child exited (status {})", code)
                     }
-                    Status::Signaled(signal) => println!("Signaled {}", signal),
+                    Status::Signaled(signal) => {
+                        self.stop_signal = None;
+                        println!("Signaled {}", signal)
+                    }
                 };
             }
             Err(err) => println!("Error continuing the program. {}", err),
@@ -89,23 +324,45 @@ impl Debugger {
                     if self.inferior.is_some() {
                         self.inferior.as_mut().unwrap().kill();
                     }
-                    if let Some(inferior) = Inferior::new(&self.target, &args, &self.breakpoints) {
+                    let mut breakpoints = self.breakpoints.clone();
+                    breakpoints.extend(self.traces.keys());
+                    if let Some(inferior) = Inferior::new(&self.target, &args, &breakpoints) {
                         // Create the inferior
                         self.inferior = Some(inferior);
-                        let result = self.inferior.as_mut().unwrap().cont();
-                        self.print_inferior_run_result(result);
+                        self.last_registers = None;
+                        self.cont_inferior(None);
                     } else {
                         println!("Error starting subprocess");
                     }
                 }
                 DebuggerCommand::Cont => {
                     if self.inferior.is_some() {
-                        let result = self.inferior.as_mut().unwrap().cont();
-                        self.print_inferior_run_result(result);
+                        self.cont_inferior(None);
+                    } else {
+                        println!("No child process under debugging");
+                    }
+                }
+                DebuggerCommand::ContSkippingSignal(signal_name, num_to_skip) => {
+                    let signal = match parse_signal(&signal_name) {
+                        Some(signal) => signal,
+                        None => {
+                            println!("Unknown signal {}", signal_name);
+                            continue;
+                        }
+                    };
+                    if self.inferior.is_some() {
+                        self.cont_inferior(Some((signal, num_to_skip)));
                     } else {
                         println!("No child process under debugging");
                     }
                 }
+                DebuggerCommand::Step => match self.inferior.as_mut() {
+                    Some(inferior) => {
+                        let result = inferior.step(&self.debug_data, self.step_inlined);
+                        self.print_inferior_run_result(result);
+                    }
+                    None => println!("No child process under debugging"),
+                },
                 DebuggerCommand::Backtrace => {
                     match &self.inferior {
                         Some(inf) => {
@@ -126,6 +383,110 @@ impl Debugger {
                         None => println!("Failed to parse a breakpoint"),
                     };
                 }
+                DebuggerCommand::Trace(func_name) => {
+                    match self.debug_data.get_addr_for_function(None, &func_name) {
+                        Some(addr) => {
+                            println!("Tracing {} at {:#x}", func_name, addr);
+                            if let Some(inferior) = self.inferior.as_mut() {
+                                inferior.set_breakpoint(addr);
+                            }
+                            self.traces.insert(addr, func_name);
+                        }
+                        None => println!("Unknown function {}", func_name),
+                    };
+                }
+                DebuggerCommand::Untrace(func_name) => {
+                    let addr = self
+                        .traces
+                        .iter()
+                        .find(|(_, name)| **name == func_name)
+                        .map(|(addr, _)| *addr);
+                    match addr {
+                        Some(addr) => {
+                            self.traces.remove(&addr);
+                            // Leave the breakpoint in place if the user set one there too.
+                            if !self.breakpoints.contains(&addr) {
+                                if let Some(inferior) = self.inferior.as_mut() {
+                                    inferior.remove_breakpoint(addr);
+                                }
+                            }
+                            println!("Stopped tracing {}", func_name);
+                        }
+                        None => println!("{} is not being traced", func_name),
+                    };
+                }
+                DebuggerCommand::WhatIs(name) => {
+                    match self.debug_data.get_function_signature(&name) {
+                        Some(signature) => println!("{}", signature),
+                        None => println!("No function named {}", name),
+                    };
+                }
+                DebuggerCommand::DumpDies(func_name) => {
+                    match DwarfData::dump_function_dies(&self.target, &func_name) {
+                        Ok(Some(dump)) => print!("{}", dump),
+                        Ok(None) => println!("No function named {}", func_name),
+                        Err(err) => println!("Failed to read DWARF info: {:?}", err),
+                    };
+                }
+                DebuggerCommand::Stack(num_words) => {
+                    self.print_stack(num_words.unwrap_or(DEFAULT_STACK_WORDS));
+                }
+                DebuggerCommand::InfoFunctionLines(func_name) => {
+                    // Default to the function the inferior is stopped in.
+                    let func_name = match func_name {
+                        Some(name) => name,
+                        None => match self.current_function() {
+                            Some(name) => name,
+                            None => {
+                                println!("No function given and no child process stopped");
+                                continue;
+                            }
+                        },
+                    };
+                    match self.debug_data.get_function_lines(&func_name) {
+                        Some(lines) if lines.is_empty() => {
+                            println!("No line information for {}", func_name)
+                        }
+                        Some(lines) => {
+                            println!("Lines with code in {}:", func_name);
+                            for line in lines {
+                                println!("  {} (at {:#x})", line, line.address);
+                            }
+                        }
+                        None => println!("No function named {}", func_name),
+                    };
+                }
+                DebuggerCommand::InfoSources => {
+                    let sources = self.debug_data.get_source_files();
+                    if sources.is_empty() {
+                        println!("No source files found (is the target stripped?)");
+                    }
+                    for source in sources {
+                        println!("{}", source);
+                    }
+                }
+                DebuggerCommand::Set(setting, value) => {
+                    let enabled = match value.as_str() {
+                        "on" => true,
+                        "off" => false,
+                        _ => {
+                            println!("Usage: set {} on|off", setting);
+                            continue;
+                        }
+                    };
+                    match setting.as_str() {
+                        "track-registers" => {
+                            self.track_registers = enabled;
+                            self.last_registers = None;
+                            if enabled && self.inferior.is_some() {
+                                // Take a snapshot now so the next stop has something to diff.
+                                self.print_changed_registers();
+                            }
+                        }
+                        "step-inlined" => self.step_inlined = enabled,
+                        _ => println!("Unknown setting {}", setting),
+                    };
+                }
                 DebuggerCommand::Quit => {
                     if self.inferior.is_some() {
                         self.inferior.as_mut().unwrap().kill();
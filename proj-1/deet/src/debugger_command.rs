@@ -2,8 +2,18 @@ pub enum DebuggerCommand {
     Quit,
     Run(Vec<String>),
     Cont,
+    ContSkippingSignal(String, usize),
+    Step,
     Backtrace,
     BreakPoint(String),
+    Set(String, String),
+    InfoSources,
+    InfoFunctionLines(Option<String>),
+    Trace(String),
+    Untrace(String),
+    Stack(Option<usize>),
+    WhatIs(String),
+    DumpDies(String),
 }
 
 impl DebuggerCommand {
@@ -16,7 +26,15 @@ impl DebuggerCommand {
                     args.iter().map(|s| s.to_string()).collect(),
                 ))
             }
-            "c" | "cont" | "continue" => Some(DebuggerCommand::Cont),
+            "c" | "cont" | "continue" => match tokens.len() {
+                1 => Some(DebuggerCommand::Cont),
+                3 => Some(DebuggerCommand::ContSkippingSignal(
+                    tokens[1].to_string(),
+                    tokens[2].parse().ok()?,
+                )),
+                _ => None,
+            },
+            "s" | "step" => Some(DebuggerCommand::Step),
             "bt" | "back" | "backtrace" => Some(DebuggerCommand::Backtrace),
             "b" | "break" => {
                 let mut addr = tokens[1];
@@ -25,6 +43,33 @@ impl DebuggerCommand {
                 }
                 Some(DebuggerCommand::BreakPoint(addr.to_string()))
             }
+            "trace" => Some(DebuggerCommand::Trace(tokens.get(1)?.to_string())),
+            "untrace" => Some(DebuggerCommand::Untrace(tokens.get(1)?.to_string())),
+            // Hidden command for debugging deet itself; only available in debug builds.
+            "dump-dies" if cfg!(debug_assertions) => {
+                Some(DebuggerCommand::DumpDies(tokens.get(1)?.to_string()))
+            }
+            "whatis" | "ptype" => Some(DebuggerCommand::WhatIs(tokens.get(1)?.to_string())),
+            "stack" => match tokens.get(1) {
+                Some(num_words) => Some(DebuggerCommand::Stack(Some(num_words.parse().ok()?))),
+                None => Some(DebuggerCommand::Stack(None)),
+            },
+            "info" => match tokens.get(1) {
+                Some(&"sources") => Some(DebuggerCommand::InfoSources),
+                Some(&"function-lines") => Some(DebuggerCommand::InfoFunctionLines(
+                    tokens.get(2).map(|name| name.to_string()),
+                )),
+                _ => None,
+            },
+            "set" => {
+                if tokens.len() != 3 {
+                    return None;
+                }
+                Some(DebuggerCommand::Set(
+                    tokens[1].to_string(),
+                    tokens[2].to_string(),
+                ))
+            }
             // Default case:
             _ => None,
         }
@@ -1,7 +1,9 @@
 use crate::gimli_wrapper;
 use addr2line::Context;
 use object::Object;
+use std::collections::BTreeSet;
 use std::convert::TryInto;
+use std::ops::Range;
 use std::{fmt, fs};
 
 #[derive(Debug)]
@@ -27,20 +29,39 @@ impl From<gimli_wrapper::Error> for Error {
     }
 }
 
+/// Maps the executable at path into memory and calls f with the parsed object file.
+fn with_object_file<T>(
+    path: &str,
+    f: impl FnOnce(&object::File, gimli::RunTimeEndian) -> Result<T, Error>,
+) -> Result<T, Error> {
+    let file = fs::File::open(path).or(Err(Error::ErrorOpeningFile))?;
+    let mmap = unsafe { memmap::Mmap::map(&file).or(Err(Error::ErrorOpeningFile))? };
+    let object = object::File::parse(&*mmap)
+        .or_else(|e| Err(gimli_wrapper::Error::ObjectError(e.to_string())))?;
+    let endian = if object.is_little_endian() {
+        gimli::RunTimeEndian::Little
+    } else {
+        gimli::RunTimeEndian::Big
+    };
+    f(&object, endian)
+}
+
 impl DwarfData {
     pub fn from_file(path: &str) -> Result<DwarfData, Error> {
-        let file = fs::File::open(path).or(Err(Error::ErrorOpeningFile))?;
-        let mmap = unsafe { memmap::Mmap::map(&file).or(Err(Error::ErrorOpeningFile))? };
-        let object = object::File::parse(&*mmap)
-            .or_else(|e| Err(gimli_wrapper::Error::ObjectError(e.to_string())))?;
-        let endian = if object.is_little_endian() {
-            gimli::RunTimeEndian::Little
-        } else {
-            gimli::RunTimeEndian::Big
-        };
-        Ok(DwarfData {
-            files: gimli_wrapper::load_file(&object, endian)?,
-            addr2line: Context::new(&object).or_else(|e| Err(gimli_wrapper::Error::from(e)))?,
+        with_object_file(path, |object, endian| {
+            Ok(DwarfData {
+                files: gimli_wrapper::load_file(object, endian)?,
+                addr2line: Context::new(object)
+                    .or_else(|e| Err(gimli_wrapper::Error::from(e)))?,
+            })
+        })
+    }
+
+    /// Re-reads the debugging info of the executable at path and dumps the raw DIE tree of the
+    /// named function. Returns Ok(None) if the function doesn't exist.
+    pub fn dump_function_dies(path: &str, func_name: &str) -> Result<Option<String>, Error> {
+        with_object_file(path, |object, endian| {
+            Ok(gimli_wrapper::dump_function_dies(object, endian, func_name)?)
         })
     }
 
@@ -111,6 +132,86 @@ impl DwarfData {
         Some(frame.function?.raw_name().ok()?.to_string())
     }
 
+    /// Returns the names of every function with debugging info.
+    pub fn get_function_names(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .flat_map(|file| file.functions.iter())
+            .map(|func| func.name.clone())
+            .collect()
+    }
+
+    fn get_function(&self, func_name: &str) -> Option<&Function> {
+        self.files
+            .iter()
+            .flat_map(|file| file.functions.iter())
+            .find(|func| func.name == func_name)
+    }
+
+    /// Returns the formal parameters of the named function, in declaration order. Parameters whose
+    /// type couldn't be resolved are still included, with an unknown type.
+    pub fn get_function_parameters(&self, func_name: &str) -> Option<Vec<Variable>> {
+        Some(self.get_function(func_name)?.parameters.clone())
+    }
+
+    /// Returns the lines that have code within the named function, sorted by line number, along
+    /// with the first address for each. Returns None if there is no such function.
+    pub fn get_function_lines(&self, func_name: &str) -> Option<Vec<Line>> {
+        for file in &self.files {
+            if let Some(func) = file.functions.iter().find(|func| func.name == func_name) {
+                let end = func.address + func.text_length;
+                let mut lines: Vec<Line> = file
+                    .lines
+                    .iter()
+                    .filter(|line| line.address >= func.address && line.address < end)
+                    .cloned()
+                    .collect();
+                lines.sort_by_key(|line| (line.number, line.address));
+                lines.dedup_by_key(|line| line.number);
+                return Some(lines);
+            }
+        }
+        None
+    }
+
+    /// Returns a C-style signature for the named function, e.g. "int add(int a, int b)". A
+    /// parameter whose type couldn't be resolved is shown as "<unknown type> name".
+    pub fn get_function_signature(&self, func_name: &str) -> Option<String> {
+        let func = self.get_function(func_name)?;
+        let return_type = match &func.return_type {
+            Some(return_type) => return_type.clone(),
+            None => Type::new("void".to_string(), 0),
+        };
+        let params: Vec<String> = func
+            .parameters
+            .iter()
+            .map(|param| declaration(&param.entity_type, &param.name))
+            .collect();
+        Some(declaration(
+            &return_type,
+            &format!("{}({})", func.name, params.join(", ")),
+        ))
+    }
+
+    /// Returns whether the code at addr was inlined from another function.
+    pub fn is_inlined(&self, addr: usize) -> bool {
+        self.files
+            .iter()
+            .flat_map(|file| file.inline_ranges.iter())
+            .any(|range| range.contains(&addr))
+    }
+
+    /// Returns every source file path referenced by the line table, sorted and deduplicated.
+    pub fn get_source_files(&self) -> Vec<String> {
+        let mut paths: BTreeSet<String> = BTreeSet::new();
+        for file in &self.files {
+            for path in &file.source_files {
+                paths.insert(path.clone());
+            }
+        }
+        paths.into_iter().collect()
+    }
+
     #[allow(dead_code)]
     pub fn print(&self) {
         for file in &self.files {
@@ -132,6 +233,12 @@ impl DwarfData {
                     "  * {} (declared on line {}, located at {:#x}, {} bytes long)",
                     func.name, func.line_number, func.address, func.text_length
                 );
+                for param in &func.parameters {
+                    println!(
+                        "    * Parameter: {} ({}, located at {}, declared at line {})",
+                        param.name, param.entity_type.name, param.location, param.line_number
+                    );
+                }
                 for var in &func.variables {
                     println!(
                         "    * Variable: {} ({}, located at {}, declared at line {})",
@@ -148,10 +255,23 @@ impl DwarfData {
     }
 }
 
+/// Declares name as having entity_type the way C does, with no space after a *, e.g. "char *s".
+fn declaration(entity_type: &Type, name: &str) -> String {
+    if entity_type.name.ends_with('*') {
+        format!("{}{}", entity_type.name, name)
+    } else {
+        format!("{} {}", entity_type.name, name)
+    }
+}
+
+const UNKNOWN_TYPE_NAME: &str = "<unknown type>";
+
 #[derive(Debug, Clone, Default)]
 pub struct Type {
     pub name: String,
     pub size: usize,
+    pub is_float: bool, // Floating-point values are passed in xmm registers, not general ones
+    pub is_pointer: bool,
 }
 
 impl Type {
@@ -159,14 +279,27 @@ impl Type {
         Type {
             name: name,
             size: size,
+            is_float: false,
+            is_pointer: false,
         }
     }
+
+    /// Placeholder for a type we couldn't resolve. Only base and pointer types are parsed, so this
+    /// covers structs, typedefs, and so on.
+    pub fn unknown() -> Self {
+        Type::new(UNKNOWN_TYPE_NAME.to_string(), 0)
+    }
+
+    pub fn is_unknown(&self) -> bool {
+        self.name == UNKNOWN_TYPE_NAME
+    }
 }
 
 #[derive(Clone)]
 pub enum Location {
     Address(usize),
     FramePointerOffset(isize),
+    Unknown, // Only used for parameters, which we keep even when DWARF doesn't say where they are
 }
 
 impl fmt::Display for Location {
@@ -174,6 +307,7 @@ impl fmt::Display for Location {
         match *self {
             Location::Address(addr) => write!(f, "Address({:#x})", addr),
             Location::FramePointerOffset(offset) => write!(f, "FramePointerOffset({})", offset),
+            Location::Unknown => write!(f, "Unknown"),
         }
     }
 }
@@ -199,6 +333,8 @@ pub struct Function {
     pub address: usize,
     pub text_length: usize,
     pub line_number: usize, // Line number in source file
+    pub return_type: Option<Type>, // None for functions returning void
+    pub parameters: Vec<Variable>,
     pub variables: Vec<Variable>,
 }
 
@@ -208,6 +344,8 @@ pub struct File {
     pub global_variables: Vec<Variable>,
     pub functions: Vec<Function>,
     pub lines: Vec<Line>,
+    pub source_files: Vec<String>, // Paths referenced by the line table
+    pub inline_ranges: Vec<Range<usize>>, // Code inlined from other functions
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -223,4 +361,85 @@ impl fmt::Display for Line {
     }
 }
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+    use std::process::Command;
+
+    const SOURCE: &str = "struct pair { long a; long b; };
+int f(char *s, struct pair p, int n) { int local = n; return local + s[0] + p.a; }
+const char *g(const char *s, char *const *v) { return v ? s : 0; }
+int none(void) { return 0; }
+static inline __attribute__((always_inline)) int twice(int n) { return n * 2; }
+int main(int argc, char **argv) {
+    struct pair p = {1, 2};
+    return f(argv[0], p, twice(argc)) + *g(\"hi\", 0) + none();
+}
+";
+
+    // Same flags as the Makefile uses for the samples
+    const CFLAGS: &[&str] = &["-O0", "-g", "-no-pie", "-fno-omit-frame-pointer"];
+
+    /// Compiles SOURCE and loads its debugging info.
+    fn load_sample(name: &str) -> DwarfData {
+        let source_path = env::temp_dir().join(format!("deet_{}.c", name));
+        let binary_path = env::temp_dir().join(format!("deet_{}", name));
+        fs::write(&source_path, SOURCE).unwrap();
+        let status = Command::new("cc")
+            .args(CFLAGS)
+            .arg("-o")
+            .arg(&binary_path)
+            .arg(&source_path)
+            .status()
+            .expect("Failed to run cc");
+        assert!(status.success());
+        DwarfData::from_file(binary_path.to_str().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_function_parameters() {
+        let debug_data = load_sample("parameters");
+        let params = debug_data.get_function_parameters("f").unwrap();
+        let names: Vec<&str> = params.iter().map(|param| param.name.as_str()).collect();
+        assert_eq!(names, vec!["s", "p", "n"]);
+        assert_eq!(params[0].entity_type.name, "char *");
+        assert!(params[1].entity_type.is_unknown());
+        assert_eq!(params[2].entity_type.name, "int");
+        assert!(debug_data.get_function_parameters("none").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_function_signature() {
+        let debug_data = load_sample("signature");
+        assert_eq!(
+            debug_data.get_function_signature("f").unwrap(),
+            "int f(char *s, <unknown type> p, int n)"
+        );
+        assert_eq!(
+            debug_data.get_function_signature("g").unwrap(),
+            "const char *g(const char *s, char *const *v)"
+        );
+        assert_eq!(debug_data.get_function_signature("none").unwrap(), "int none()");
+        assert_eq!(
+            debug_data.get_function_signature("main").unwrap(),
+            "int main(int argc, char **argv)"
+        );
+        assert!(debug_data.get_function_signature("missing").is_none());
+    }
 
+    #[test]
+    fn test_inline_ranges() {
+        let debug_data = load_sample("inline");
+        let ranges: Vec<&Range<usize>> = debug_data
+            .files
+            .iter()
+            .flat_map(|file| file.inline_ranges.iter())
+            .collect();
+        assert_eq!(ranges.len(), 1);
+        assert!(debug_data.is_inlined(ranges[0].start));
+        assert!(!debug_data.is_inlined(ranges[0].end));
+        let main = debug_data.get_addr_for_function(None, "main").unwrap();
+        assert!(!debug_data.is_inlined(main));
+    }
+}
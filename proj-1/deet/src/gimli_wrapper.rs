@@ -16,7 +16,12 @@ use std::convert::TryInto;
 use std::fmt::Write;
 use std::{io, path};
 
-pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<Vec<File>, Error> {
+/// Loads the DWARF sections of object and calls f with them.
+fn with_dwarf<T>(
+    object: &object::File,
+    endian: gimli::RunTimeEndian,
+    f: impl FnOnce(&gimli::Dwarf<gimli::EndianSlice<gimli::RunTimeEndian>>) -> Result<T, Error>,
+) -> Result<T, Error> {
     // Load a section and return as `Cow<[u8]>`.
     let load_section = |id: gimli::SectionId| -> Result<borrow::Cow<[u8]>, gimli::Error> {
         Ok(object
@@ -39,6 +44,16 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
     // Create `EndianSlice`s for all of the sections.
     let dwarf = dwarf_cow.borrow(&borrow_section);
 
+    f(&dwarf)
+}
+
+pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<Vec<File>, Error> {
+    with_dwarf(object, endian, load_units)
+}
+
+fn load_units(
+    dwarf: &gimli::Dwarf<gimli::EndianSlice<gimli::RunTimeEndian>>,
+) -> Result<Vec<File>, Error> {
     // Define a mapping from type offsets to type structs
     let mut offset_to_type: HashMap<usize, Type> = HashMap::new();
 
@@ -49,8 +64,66 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
     while let Some(header) = iter.next()? {
         let unit = dwarf.unit(header)?;
 
+        // Collect the type DIEs first, since a DIE can refer to a type that's declared after it,
+        // and a pointer can point to a type that's declared after the pointer.
+        let mut type_dies: HashMap<usize, TypeDie> = HashMap::new();
+        let mut entries = unit.entries();
+        while let Some((_, entry)) = entries.next_dfs()? {
+            match entry.tag() {
+                gimli::DW_TAG_base_type
+                | gimli::DW_TAG_pointer_type
+                | gimli::DW_TAG_const_type => {}
+                _ => continue,
+            }
+            let name = if let Ok(Some(attr)) = entry.attr(gimli::DW_AT_name) {
+                if let Ok(DebugValue::Str(name)) = get_attr_value(&attr, &unit, dwarf) {
+                    name
+                } else {
+                    "<unknown>".to_string()
+                }
+            } else {
+                "<unknown>".to_string()
+            };
+            let byte_size = if let Ok(Some(attr)) = entry.attr(gimli::DW_AT_byte_size) {
+                if let Ok(DebugValue::Uint(byte_size)) = get_attr_value(&attr, &unit, dwarf) {
+                    byte_size
+                } else {
+                    // TODO: report error?
+                    0
+                }
+            } else {
+                // TODO: report error?
+                0
+            };
+            let target = match entry.attr(gimli::DW_AT_type) {
+                Ok(Some(attr)) => match get_attr_value(&attr, &unit, dwarf) {
+                    Ok(DebugValue::Size(offset)) => Some(offset),
+                    _ => None,
+                },
+                _ => None,
+            };
+            type_dies.insert(
+                entry.offset().0,
+                TypeDie {
+                    tag: entry.tag(),
+                    name,
+                    byte_size: byte_size.try_into().unwrap(),
+                    is_float: matches!(
+                        entry.attr_value(gimli::DW_AT_encoding)?,
+                        Some(gimli::AttributeValue::Encoding(gimli::DW_ATE_float))
+                    ),
+                    target,
+                },
+            );
+        }
+        for offset in type_dies.keys() {
+            offset_to_type.insert(*offset, resolve_type(*offset, &type_dies));
+        }
+
         // Iterate over the Debugging Information Entries (DIEs) in the unit.
         let mut depth = 0;
+        // Depth of the most recent subprogram, so we can tell which DIEs are its parameters
+        let mut subprogram_depth: Option<isize> = None;
         let mut entries = unit.entries();
         while let Some((delta_depth, entry)) = entries.next_dfs()? {
             depth += delta_depth;
@@ -59,7 +132,7 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
             match entry.tag() {
                 gimli::DW_TAG_compile_unit => {
                     let name = if let Ok(Some(attr)) = entry.attr(gimli::DW_AT_name) {
-                        if let Ok(DebugValue::Str(name)) = get_attr_value(&attr, &unit, &dwarf) {
+                        if let Ok(DebugValue::Str(name)) = get_attr_value(&attr, &unit, dwarf) {
                             name
                         } else {
                             "<unknown>".to_string()
@@ -72,40 +145,15 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
                         global_variables: Vec::new(),
                         functions: Vec::new(),
                         lines: Vec::new(),
+                        source_files: Vec::new(),
+                        inline_ranges: Vec::new(),
                     });
                 }
-                gimli::DW_TAG_base_type => {
-                    let name = if let Ok(Some(attr)) = entry.attr(gimli::DW_AT_name) {
-                        if let Ok(DebugValue::Str(name)) = get_attr_value(&attr, &unit, &dwarf) {
-                            name
-                        } else {
-                            "<unknown>".to_string()
-                        }
-                    } else {
-                        "<unknown>".to_string()
-                    };
-                    let byte_size = if let Ok(Some(attr)) = entry.attr(gimli::DW_AT_byte_size) {
-                        if let Ok(DebugValue::Uint(byte_size)) =
-                            get_attr_value(&attr, &unit, &dwarf)
-                        {
-                            byte_size
-                        } else {
-                            // TODO: report error?
-                            0
-                        }
-                    } else {
-                        // TODO: report error?
-                        0
-                    };
-                    let type_offset = entry.offset().0;
-                    offset_to_type
-                        .insert(type_offset, Type::new(name, byte_size.try_into().unwrap()));
-                }
                 gimli::DW_TAG_subprogram => {
                     let mut func: Function = Default::default();
                     let mut attrs = entry.attrs();
                     while let Some(attr) = attrs.next()? {
-                        let val = get_attr_value(&attr, &unit, &dwarf);
+                        let val = get_attr_value(&attr, &unit, dwarf);
                         //println!("   {}: {:?}", attr.name(), val);
                         match attr.name() {
                             gimli::DW_AT_name => {
@@ -129,10 +177,21 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
                                     func.line_number = line_number.try_into().unwrap();
                                 }
                             }
+                            gimli::DW_AT_type => {
+                                if let Ok(DebugValue::Size(offset)) = val {
+                                    func.return_type = Some(
+                                        offset_to_type
+                                            .get(&offset)
+                                            .cloned()
+                                            .unwrap_or_else(Type::unknown),
+                                    );
+                                }
+                            }
                             _ => {}
                         }
                     }
                     compilation_units.last_mut().unwrap().functions.push(func);
+                    subprogram_depth = Some(depth);
                 }
                 gimli::DW_TAG_formal_parameter | gimli::DW_TAG_variable => {
                     let mut name = String::new();
@@ -141,7 +200,7 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
                     let mut line_number = 0;
                     let mut attrs = entry.attrs();
                     while let Some(attr) = attrs.next()? {
-                        let val = get_attr_value(&attr, &unit, &dwarf);
+                        let val = get_attr_value(&attr, &unit, dwarf);
                         //println!("   {}: {:?}", attr.name(), val);
                         match attr.name() {
                             gimli::DW_AT_name => {
@@ -169,7 +228,25 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
                             _ => {}
                         }
                     }
-                    if entity_type.is_some() && location.is_some() {
+                    if entry.tag() == gimli::DW_TAG_formal_parameter
+                        && subprogram_depth == Some(depth - 1)
+                    {
+                        // Keep every parameter, even if we couldn't work out its type or
+                        // location, so that the list still matches the declaration.
+                        compilation_units
+                            .last_mut()
+                            .unwrap()
+                            .functions
+                            .last_mut()
+                            .unwrap()
+                            .parameters
+                            .push(Variable {
+                                name,
+                                entity_type: entity_type.unwrap_or_else(Type::unknown),
+                                location: location.unwrap_or(Location::Unknown),
+                                line_number: line_number.try_into().unwrap(),
+                            });
+                    } else if entity_type.is_some() && location.is_some() {
                         let var = Variable {
                             name,
                             entity_type: entity_type.unwrap(),
@@ -194,6 +271,16 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
                         }
                     }
                 }
+                gimli::DW_TAG_inlined_subroutine => {
+                    let mut ranges = dwarf.die_ranges(&unit, entry)?;
+                    while let Some(range) = ranges.next()? {
+                        compilation_units
+                            .last_mut()
+                            .unwrap()
+                            .inline_ranges
+                            .push(range.begin.try_into().unwrap()..range.end.try_into().unwrap());
+                    }
+                }
                 // NOTE: :You may consider supporting other types by extending this
                 // match statement
                 _ => {}
@@ -220,6 +307,15 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
                         );
                     }
 
+                    // Remember every path the line table refers to, including headers that
+                    // don't have a compilation unit of their own.
+                    if let Some(unit_file) = compilation_units.last_mut() {
+                        let path_name = path.to_string_lossy().to_string();
+                        if !path_name.is_empty() && !unit_file.source_files.contains(&path_name) {
+                            unit_file.source_files.push(path_name);
+                        }
+                    }
+
                     // Get the File
                     let file = compilation_units
                         .iter_mut()
@@ -243,6 +339,61 @@ pub fn load_file(object: &object::File, endian: gimli::RunTimeEndian) -> Result<
     Ok(compilation_units)
 }
 
+/// Returns a dump of the DIE tree for the named function's subprogram (the subprogram DIE and every
+/// DIE nested under it, with their attributes), or None if there is no such function. This is a
+/// debugging aid for working on the parsing code above.
+pub fn dump_function_dies(
+    object: &object::File,
+    endian: gimli::RunTimeEndian,
+    func_name: &str,
+) -> Result<Option<String>, Error> {
+    with_dwarf(object, endian, |dwarf| {
+        let mut iter = dwarf.units();
+        while let Some(header) = iter.next()? {
+            let unit = dwarf.unit(header)?;
+            let mut output = String::new();
+            let mut depth = 0;
+            let mut subprogram_depth: Option<isize> = None;
+            let mut entries = unit.entries();
+            while let Some((delta_depth, entry)) = entries.next_dfs()? {
+                depth += delta_depth;
+                match subprogram_depth {
+                    // We've walked past the last child of the subprogram.
+                    Some(start) if depth <= start => return Ok(Some(output)),
+                    Some(_) => {}
+                    None => {
+                        if entry.tag() != gimli::DW_TAG_subprogram {
+                            continue;
+                        }
+                        let name = match entry.attr(gimli::DW_AT_name)? {
+                            Some(attr) => get_attr_value(&attr, &unit, dwarf)?,
+                            None => DebugValue::NoVal,
+                        };
+                        match name {
+                            DebugValue::Str(ref name) if name == func_name => {
+                                subprogram_depth = Some(depth)
+                            }
+                            _ => continue,
+                        }
+                    }
+                }
+
+                let indent = "  ".repeat((depth - subprogram_depth.unwrap()) as usize);
+                writeln!(output, "{}<{:#x}> {}", indent, entry.offset().0, entry.tag())?;
+                let mut attrs = entry.attrs();
+                while let Some(attr) = attrs.next()? {
+                    let val = get_attr_value(&attr, &unit, dwarf).unwrap_or(DebugValue::NoVal);
+                    writeln!(output, "{}    {}: {:?}", indent, attr.name(), val)?;
+                }
+            }
+            if subprogram_depth.is_some() {
+                return Ok(Some(output));
+            }
+        }
+        Ok(None)
+    })
+}
+
 #[derive(Debug, Clone)]
 pub enum DebugValue {
     Str(String),
@@ -291,6 +442,56 @@ impl<'input, Endian> Reader for gimli::EndianSlice<'input, Endian> where
 
 trait Reader: gimli::Reader<Offset = usize> + Send + Sync {}
 
+/// A type DIE, kept until every type in the unit has been seen so it can be resolved by offset.
+struct TypeDie {
+    tag: gimli::DwTag,
+    name: String,
+    byte_size: usize,
+    is_float: bool,
+    target: Option<usize>, // The type a pointer points to or a const qualifies
+}
+
+/// Builds the type at offset, following pointers and consts down to the type they refer to.
+fn resolve_type(offset: usize, type_dies: &HashMap<usize, TypeDie>) -> Type {
+    let die = match type_dies.get(&offset) {
+        Some(die) => die,
+        None => return Type::unknown(),
+    };
+    let target = die.target.map(|target| resolve_type(target, type_dies));
+    match die.tag {
+        gimli::DW_TAG_pointer_type => {
+            // A pointer without a type is a void pointer.
+            let pointee_name = target.map_or("void".to_string(), |pointee| pointee.name);
+            let name = if pointee_name.ends_with('*') {
+                format!("{}*", pointee_name)
+            } else {
+                format!("{} *", pointee_name)
+            };
+            let mut pointer_type = Type::new(name, 8);
+            pointer_type.is_pointer = true;
+            pointer_type
+        }
+        gimli::DW_TAG_const_type => match target {
+            // A const pointer is written with the const after the *, e.g. "char *const".
+            Some(target) if target.is_pointer => Type {
+                name: format!("{}const", target.name),
+                ..target
+            },
+            Some(target) if !target.is_unknown() => Type {
+                name: format!("const {}", target.name),
+                ..target
+            },
+            Some(target) => target,
+            None => Type::new("const void".to_string(), 0),
+        },
+        _ => {
+            let mut base_type = Type::new(die.name.clone(), die.byte_size);
+            base_type.is_float = die.is_float;
+            base_type
+        }
+    }
+}
+
 fn get_location<R: Reader>(attr: &gimli::Attribute<R>, unit: &gimli::Unit<R>) -> Option<Location> {
     if let gimli::AttributeValue::Exprloc(ref data) = attr.value() {
         let encoding = unit.encoding();
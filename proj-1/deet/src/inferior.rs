@@ -1,4 +1,5 @@
 use crate::dwarf_data::{DwarfData, Line};
+use libc::user_regs_struct;
 use nix::sys::ptrace;
 use nix::sys::signal;
 use nix::sys::wait::{waitpid, WaitPidFlag, WaitStatus};
@@ -31,6 +32,50 @@ fn child_traceme() -> Result<(), std::io::Error> {
     )))
 }
 
+/// Returns the name and value of each general purpose register, in the order they are laid out
+/// in user_regs_struct.
+pub fn register_values(regs: &user_regs_struct) -> Vec<(&'static str, u64)> {
+    vec![
+        ("rax", regs.rax),
+        ("rbx", regs.rbx),
+        ("rcx", regs.rcx),
+        ("rdx", regs.rdx),
+        ("rsi", regs.rsi),
+        ("rdi", regs.rdi),
+        ("rbp", regs.rbp),
+        ("rsp", regs.rsp),
+        ("r8", regs.r8),
+        ("r9", regs.r9),
+        ("r10", regs.r10),
+        ("r11", regs.r11),
+        ("r12", regs.r12),
+        ("r13", regs.r13),
+        ("r14", regs.r14),
+        ("r15", regs.r15),
+        ("rip", regs.rip),
+        ("eflags", regs.eflags),
+        ("orig_rax", regs.orig_rax),
+        ("cs", regs.cs),
+        ("ss", regs.ss),
+        ("ds", regs.ds),
+        ("es", regs.es),
+        ("fs", regs.fs),
+        ("gs", regs.gs),
+        ("fs_base", regs.fs_base),
+        ("gs_base", regs.gs_base),
+    ]
+}
+
+/// Registers holding the first six integer/pointer arguments on function entry, per the System V
+/// x86-64 calling convention.
+pub const ARGUMENT_REGISTERS: [&str; 6] = ["rdi", "rsi", "rdx", "rcx", "r8", "r9"];
+
+/// Where one frame on the stack keeps its saved rbp and its return address.
+pub struct FrameSlots {
+    pub saved_rbp: Option<usize>, // None if the function hasn't pushed rbp yet
+    pub return_address: usize,
+}
+
 fn align_addr_to_word(addr: usize) -> usize {
     addr & (-(size_of::<usize>() as isize) as usize)
 }
@@ -74,6 +119,11 @@ impl Inferior {
         nix::unistd::Pid::from_raw(self.child.id() as i32)
     }
 
+    /// Returns the current register state of the inferior.
+    pub fn get_registers(&self) -> Result<user_regs_struct, nix::Error> {
+        ptrace::getregs(self.pid())
+    }
+
     /// Kills the child process if running.
     pub fn kill(&mut self) {
         if let Ok(()) = self.child.kill() {
@@ -97,7 +147,9 @@ impl Inferior {
         })
     }
 
-    pub fn cont(&mut self) -> Result<Status, nix::Error> {
+    /// Resumes the inferior, delivering the given signal to it (or none), and waits for it to stop
+    /// again.
+    pub fn cont(&mut self, signal: Option<signal::Signal>) -> Result<Status, nix::Error> {
         // Check if the child process stopped at the breakpoint
         let mut registers = ptrace::getregs(self.pid())?;
         let rip_addr = registers.rip as usize;
@@ -137,10 +189,72 @@ impl Inferior {
             // Resume the rest of execution.
         }
 
-        ptrace::cont(self.pid(), None)?;
+        ptrace::cont(self.pid(), signal)?;
         self.wait(None)
     }
 
+    /// Steps the inferior until it reaches a different source line, and returns the status it
+    /// stopped with. Unless step_inlined is set, code inlined from other functions is treated as
+    /// part of the line it was inlined into rather than stopped in.
+    pub fn step(
+        &mut self,
+        debug_data: &DwarfData,
+        step_inlined: bool,
+    ) -> Result<Status, nix::Error> {
+        // If stopped at a breakpoint, back up so the instruction under it runs next.
+        let mut registers = ptrace::getregs(self.pid())?;
+        let mut rip = registers.rip as usize;
+        if self.breakpoints_original_instr.contains_key(&(rip - 1)) {
+            rip -= 1;
+            registers.rip = rip as u64;
+            ptrace::setregs(self.pid(), registers)?;
+        }
+        let start_line = debug_data.get_line_from_addr(rip);
+
+        loop {
+            match self.step_instruction()? {
+                Status::Stopped(signal::Signal::SIGTRAP, new_rip) => rip = new_rip,
+                status => return Ok(status),
+            }
+            if !step_inlined && debug_data.is_inlined(rip) {
+                continue;
+            }
+            if let Some(line) = debug_data.get_line_from_addr(rip) {
+                match &start_line {
+                    Some(start) if start.file == line.file && start.number == line.number => {}
+                    _ => break,
+                }
+            }
+        }
+
+        // Landing on a breakpoint leaves the inferior as though it had just hit it, which is the
+        // state cont expects to find it in.
+        if self.breakpoints_original_instr.contains_key(&rip) {
+            let mut registers = ptrace::getregs(self.pid())?;
+            rip += 1;
+            registers.rip = rip as u64;
+            ptrace::setregs(self.pid(), registers)?;
+        }
+        Ok(Status::Stopped(signal::Signal::SIGTRAP, rip))
+    }
+
+    /// Executes the one instruction at rip and waits for the inferior to stop again. If there is a
+    /// breakpoint on that instruction, the original instruction runs and the breakpoint is put back
+    /// afterwards.
+    fn step_instruction(&mut self) -> Result<Status, nix::Error> {
+        let rip = ptrace::getregs(self.pid())?.rip as usize;
+        let orig_instr = self.breakpoints_original_instr.get(&rip).copied();
+        if let Some(instr) = orig_instr {
+            self.write_byte(rip, instr)?;
+        }
+        ptrace::step(self.pid(), None)?;
+        let status = self.wait(None)?;
+        if orig_instr.is_some() {
+            self.set_breakpoint(rip);
+        }
+        Ok(status)
+    }
+
     pub fn print_backtrace(&self, debug_data: &DwarfData) -> Result<(), nix::Error> {
         let mut instruction_ptr = ptrace::getregs(self.pid())?.rip as usize;
         let mut base_ptr = ptrace::getregs(self.pid())?.rbp as usize;
@@ -162,13 +276,62 @@ impl Inferior {
                 break;
             }
 
-            instruction_ptr =
-                ptrace::read(self.pid(), (base_ptr + 8) as ptrace::AddressType)? as usize;
-            base_ptr = ptrace::read(self.pid(), base_ptr as ptrace::AddressType)? as usize;
+            instruction_ptr = self.read_word(base_ptr + 8)?;
+            base_ptr = self.read_word(base_ptr)?;
         }
         Ok(())
     }
 
+    /// Returns where each frame on the stack keeps its saved rbp and return address, innermost
+    /// first. Normally rbp points at the caller's saved rbp and the return address sits one word
+    /// above it. A function stopped on its first instruction (e.g. at a breakpoint on it) hasn't
+    /// pushed rbp yet, though, so its return address is at rsp and rbp is still the caller's.
+    pub fn get_frame_slots(&self, debug_data: &DwarfData) -> Result<Vec<FrameSlots>, nix::Error> {
+        let regs = ptrace::getregs(self.pid())?;
+        let mut instruction_ptr = regs.rip as usize;
+        // Stopping at a breakpoint leaves rip just past the int3 written over the instruction.
+        if self
+            .breakpoints_original_instr
+            .contains_key(&(instruction_ptr - 1))
+        {
+            instruction_ptr -= 1;
+        }
+        let mut base_ptr = regs.rbp as usize;
+        let mut frames = Vec::new();
+
+        if let Some(function) = debug_data.get_function_from_addr(instruction_ptr) {
+            if debug_data.get_addr_for_function(None, &function) == Some(instruction_ptr) {
+                let return_address = regs.rsp as usize;
+                frames.push(FrameSlots {
+                    saved_rbp: None,
+                    return_address,
+                });
+                if function == "main" {
+                    return Ok(frames);
+                }
+                instruction_ptr = self.read_word(return_address)?;
+            }
+        }
+        loop {
+            frames.push(FrameSlots {
+                saved_rbp: Some(base_ptr),
+                return_address: base_ptr + 8,
+            });
+            match debug_data.get_function_from_addr(instruction_ptr) {
+                Some(function) if function != "main" => {}
+                _ => break,
+            }
+            instruction_ptr = self.read_word(base_ptr + 8)?;
+            base_ptr = self.read_word(base_ptr)?;
+        }
+        Ok(frames)
+    }
+
+    /// Reads one word of the inferior's memory at addr.
+    pub fn read_word(&self, addr: usize) -> Result<usize, nix::Error> {
+        Ok(ptrace::read(self.pid(), addr as ptrace::AddressType)? as usize)
+    }
+
     /// Set the breakpoint if the child process is running.
     pub fn set_breakpoint(&mut self, addr: usize) {
         match self.child.try_wait() {
@@ -190,6 +353,15 @@ impl Inferior {
         };
     }
 
+    /// Removes the breakpoint at addr (if one was set), putting back the original instruction.
+    pub fn remove_breakpoint(&mut self, addr: usize) {
+        if let Some(orig_instr) = self.breakpoints_original_instr.remove(&addr) {
+            if let Err(err) = self.write_byte(addr, orig_instr) {
+                println!("Failed to remove the breakpoint at {}. Err: {}", addr, err);
+            }
+        }
+    }
+
     fn write_byte(&mut self, addr: usize, val: u8) -> Result<u8, nix::Error> {
         let aligned_addr = align_addr_to_word(addr);
         let byte_offset = addr - aligned_addr;
@@ -1,3 +1,4 @@
+mod completer;
 mod debugger;
 mod debugger_command;
 mod dwarf_data;
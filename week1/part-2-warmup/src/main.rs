@@ -1,40 +1,319 @@
 /* The following exercises were borrowed from Will Crichton's CS 242 Rust lab. */
 
-use std::collections::HashSet;
+// The exercises below are only exercised through `cargo test`, and keep the `&Vec` signatures
+// from the handout.
+#![allow(dead_code, clippy::ptr_arg)]
+
+use std::collections::{HashMap, HashSet};
+use std::ops::{Add, AddAssign};
 
 fn main() {
     println!("Hi! Try running \"cargo test\" to run tests.");
 }
 
-fn add_n(v: Vec<i32>, n: i32) -> Vec<i32> {
-    let mut newv: Vec<i32> = Vec::new();
+fn add_n<T: Copy + Add<Output = T>>(v: Vec<T>, n: T) -> Vec<T> {
+    let mut newv: Vec<T> = Vec::new();
 
     for num in v.iter() {
-        newv.push(num + n);
+        newv.push(*num + n);
     }
     newv
 }
 
-fn add_n_inplace(v: &mut Vec<i32>, n: i32) {
+fn add_n_inplace<T: Copy + AddAssign>(v: &mut Vec<T>, n: T) {
     let mut index:usize = 0;
     while index < v.len() {
-        v[index] = v[index] + n;
+        v[index] += n;
         index += 1;
     }
 }
 
+// Unlike add_n, this doesn't build a new vector: nothing is added until the returned iterator is
+// consumed (e.g. with collect()), and then only one element at a time.
+fn add_n_iter(v: Vec<i32>, n: i32) -> impl Iterator<Item = i32> {
+    v.into_iter().map(move |num| num + n)
+}
+
+fn add_n_checked(v: Vec<i32>, n: i32) -> Option<Vec<i32>> {
+    let mut newv: Vec<i32> = Vec::new();
+
+    for num in v.iter() {
+        newv.push(num.checked_add(n)?);
+    }
+    Some(newv)
+}
+
 fn dedup(v: &mut Vec<i32>) {
-    let mut index:usize = 1;
     let mut seen:HashSet<i32> = HashSet::new();
 
+    v.retain(|num| seen.insert(*num));
+}
+
+fn dedup_keep_last(v: &mut Vec<i32>) {
+    let mut seen: HashSet<i32> = HashSet::new();
+    let mut kept: Vec<i32> = Vec::new();
+
+    for num in v.iter().rev() {
+        if seen.insert(*num) {
+            kept.push(*num);
+        }
+    }
+    kept.reverse();
+    *v = kept;
+}
+
+fn unique(v: &Vec<i32>) -> Vec<i32> {
+    let mut newv: Vec<i32> = v.clone();
+    dedup(&mut newv);
+    newv
+}
+
+// Unlike dedup, this only removes an element if it's the same as the one right before it, so a
+// value can still appear more than once as long as something different comes in between.
+fn dedup_by<T, F: Fn(&T, &T) -> bool>(v: &mut Vec<T>, same: F) {
+    let mut kept: Vec<T> = Vec::new();
+
+    for item in v.drain(..) {
+        match kept.last() {
+            Some(last) if same(last, &item) => {}
+            _ => kept.push(item),
+        }
+    }
+    *v = kept;
+}
+
+fn swap_remove<T>(v: &mut Vec<T>, index: usize) -> T {
+    let len: usize = v.len();
+    if index >= len {
+        panic!("swap_remove index (is {}) should be < len (is {})", index, len);
+    }
+    v.swap(index, len - 1);
+    v.pop().unwrap()
+}
+
+fn longest_run<T: PartialEq>(v: &[T]) -> usize {
+    if v.is_empty() {
+        return 0;
+    }
+    let mut longest: usize = 1;
+    let mut current: usize = 1;
+    for pair in v.windows(2) {
+        if pair[0] == pair[1] {
+            current += 1;
+            if current > longest {
+                longest = current;
+            }
+        } else {
+            current = 1;
+        }
+    }
+    longest
+}
+
+fn filter_vec<T, F: Fn(&T) -> bool>(v: Vec<T>, pred: F) -> Vec<T> {
+    let mut newv: Vec<T> = Vec::new();
+
+    for item in v {
+        if pred(&item) {
+            newv.push(item);
+        }
+    }
+    newv
+}
+
+fn map_vec<T, U, F: Fn(T) -> U>(v: Vec<T>, f: F) -> Vec<U> {
+    let mut newv: Vec<U> = Vec::new();
+
+    for item in v {
+        newv.push(f(item));
+    }
+    newv
+}
+
+fn reduce<T, F: Fn(T, &T) -> T>(v: &Vec<T>, init: T, f: F) -> T {
+    let mut acc: T = init;
+
+    for item in v.iter() {
+        acc = f(acc, item);
+    }
+    acc
+}
+
+fn sum(v: &Vec<i32>) -> i32 {
+    reduce(v, 0, |acc, x| acc + x)
+}
+
+fn concat<T>(mut a: Vec<T>, mut b: Vec<T>) -> Vec<T> {
+    a.append(&mut b);
+    a
+}
+
+// Splits v into groups of size elements; the last group is shorter if size doesn't divide v's
+// length. Panics if size is 0, since no number of empty groups could hold the elements.
+fn chunk<T: Clone>(v: &Vec<T>, size: usize) -> Vec<Vec<T>> {
+    if size == 0 {
+        panic!("chunk size must be greater than 0");
+    }
+    let mut chunks: Vec<Vec<T>> = Vec::new();
+    let mut index: usize = 0;
     while index < v.len() {
-        if seen.contains(&v[index]) {
-            v.remove(index);
+        let end: usize = (index + size).min(v.len());
+        chunks.push(v[index..end].to_vec());
+        index = end;
+    }
+    chunks
+}
+
+fn flatten<T>(v: Vec<Vec<T>>) -> Vec<T> {
+    let mut newv: Vec<T> = Vec::new();
+
+    for inner in v {
+        newv.extend(inner);
+    }
+    newv
+}
+
+fn zip<T: Clone, U: Clone>(a: &Vec<T>, b: &Vec<U>) -> Vec<(T, U)> {
+    let mut pairs: Vec<(T, U)> = Vec::new();
+    let len: usize = a.len().min(b.len());
+
+    for index in 0..len {
+        pairs.push((a[index].clone(), b[index].clone()));
+    }
+    pairs
+}
+
+fn prefix_sums(v: &Vec<i32>) -> Vec<i32> {
+    let mut sums: Vec<i32> = Vec::new();
+    let mut total: i32 = 0;
+
+    for num in v.iter() {
+        total += num;
+        sums.push(total);
+    }
+    sums
+}
+
+fn remove_all(v: &mut Vec<i32>, target: i32) {
+    v.retain(|&num| num != target);
+}
+
+fn rotate_left(v: &mut Vec<i32>, k: usize) {
+    let len: usize = v.len();
+    if len == 0 {
+        return;
+    }
+    let shift: usize = k % len;
+    let mut rotated: Vec<i32> = Vec::new();
+    for index in 0..len {
+        rotated.push(v[(index + shift) % len]);
+    }
+    *v = rotated;
+}
+
+fn rotate_right(v: &mut Vec<i32>, k: usize) {
+    let len: usize = v.len();
+    if len == 0 {
+        return;
+    }
+    rotate_left(v, len - k % len);
+}
+
+fn intersperse(v: Vec<i32>, sep: i32) -> Vec<i32> {
+    let mut newv: Vec<i32> = Vec::new();
+
+    for num in v {
+        if !newv.is_empty() {
+            newv.push(sep);
+        }
+        newv.push(num);
+    }
+    newv
+}
+
+fn window_sums(v: &Vec<i32>, w: usize) -> Vec<i32> {
+    let mut sums: Vec<i32> = Vec::new();
+    if w == 0 {
+        return sums;
+    }
+    for window in v.windows(w) {
+        sums.push(window.iter().sum());
+    }
+    sums
+}
+
+fn partition<T, F: Fn(&T) -> bool>(v: Vec<T>, pred: F) -> (Vec<T>, Vec<T>) {
+    let mut matching: Vec<T> = Vec::new();
+    let mut rest: Vec<T> = Vec::new();
+
+    for item in v {
+        if pred(&item) {
+            matching.push(item);
         } else {
-            seen.insert(v[index]);
-            index += 1;
+            rest.push(item);
         }
     }
+    (matching, rest)
+}
+
+fn count_occurrences(v: &Vec<i32>) -> HashMap<i32, usize> {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+
+    for num in v.iter() {
+        *counts.entry(*num).or_insert(0) += 1;
+    }
+    counts
+}
+
+// Hand-rolled on purpose to show the two-pointer technique; in real code, use v.reverse().
+fn reverse_inplace<T>(v: &mut Vec<T>) {
+    let mut left: usize = 0;
+    let mut right: usize = v.len();
+    while left + 1 < right {
+        v.swap(left, right - 1);
+        left += 1;
+        right -= 1;
+    }
+}
+
+fn find_index(v: &Vec<i32>, target: i32) -> Option<usize> {
+    let mut index: usize = 0;
+    while index < v.len() {
+        if v[index] == target {
+            return Some(index);
+        }
+        index += 1;
+    }
+    None
+}
+
+fn group_by_consecutive<T: Clone, F: Fn(&T, &T) -> bool>(v: &Vec<T>, same: F) -> Vec<Vec<T>> {
+    let mut groups: Vec<Vec<T>> = Vec::new();
+
+    for item in v.iter() {
+        match groups.last_mut() {
+            Some(group) if same(&group[group.len() - 1], item) => group.push(item.clone()),
+            _ => groups.push(vec![item.clone()]),
+        }
+    }
+    groups
+}
+
+fn window_max(v: &Vec<i32>, w: usize) -> Vec<i32> {
+    let mut maxes: Vec<i32> = Vec::new();
+    if w == 0 {
+        return maxes;
+    }
+    for window in v.windows(w) {
+        let mut max: i32 = window[0];
+        for &num in window.iter() {
+            if num > max {
+                max = num;
+            }
+        }
+        maxes.push(max);
+    }
+    maxes
 }
 
 #[cfg(test)]
@@ -46,6 +325,25 @@ mod test {
         assert_eq!(add_n(vec![1], 2), vec![3]);
     }
 
+    #[test]
+    fn test_add_n_f64() {
+        assert_eq!(add_n(vec![1.5, -2.0], 0.5), vec![2.0, -1.5]);
+    }
+
+    #[test]
+    fn test_add_n_iter() {
+        let v = vec![1, -5, 10];
+        let collected: Vec<i32> = add_n_iter(v.clone(), 2).collect();
+        assert_eq!(collected, add_n(v, 2));
+    }
+
+    #[test]
+    fn test_add_n_checked() {
+        assert_eq!(add_n_checked(vec![1, -5], 2), Some(vec![3, -3]));
+        assert_eq!(add_n_checked(vec![1, i32::MAX], 1), None);
+        assert_eq!(add_n_checked(vec![i32::MIN], -1), None);
+    }
+
     #[test]
     fn test_add_n_inplace() {
         let mut v = vec![1];
@@ -53,10 +351,332 @@ mod test {
         assert_eq!(v, vec![3]);
     }
 
+    #[test]
+    fn test_add_n_inplace_f64() {
+        let mut v = vec![1.5, -2.0];
+        add_n_inplace(&mut v, 0.5);
+        assert_eq!(v, vec![2.0, -1.5]);
+    }
+
     #[test]
     fn test_dedup() {
         let mut v = vec![3, 1, 0, 1, 4, 4];
         dedup(&mut v);
         assert_eq!(v, vec![3, 1, 0, 4]);
     }
+
+    #[test]
+    fn test_dedup_repeated_first_element() {
+        let mut v = vec![1, 2, 1, 1, 3];
+        dedup(&mut v);
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_unique() {
+        let v = vec![3, 1, 0, 1, 4, 4];
+        assert_eq!(unique(&v), vec![3, 1, 0, 4]);
+        assert_eq!(v, vec![3, 1, 0, 1, 4, 4]);
+    }
+
+    #[test]
+    fn test_dedup_keep_last() {
+        // dedup keeps the first 1 (before the 0); dedup_keep_last keeps the second (after it).
+        let mut v = vec![3, 1, 0, 1, 4, 4];
+        dedup_keep_last(&mut v);
+        assert_eq!(v, vec![3, 0, 1, 4]);
+    }
+
+    #[test]
+    fn test_dedup_by() {
+        // The final 1 stays, since it isn't next to the other 1s (dedup would remove it).
+        let mut v = vec![1, 1, 2, 3, 3, 3, 1];
+        dedup_by(&mut v, |a, b| a == b);
+        assert_eq!(v, vec![1, 2, 3, 1]);
+
+        let mut v = vec!["a", "A", "b", "B", "a"];
+        dedup_by(&mut v, |a, b| a.eq_ignore_ascii_case(b));
+        assert_eq!(v, vec!["a", "b", "a"]);
+    }
+
+    #[test]
+    fn test_swap_remove() {
+        let mut v = vec![1, 2, 3, 4, 5];
+        assert_eq!(swap_remove(&mut v, 1), 2);
+        assert_eq!(v, vec![1, 5, 3, 4]);
+    }
+
+    #[test]
+    fn test_swap_remove_last() {
+        let mut v = vec![1, 2, 3];
+        assert_eq!(swap_remove(&mut v, 2), 3);
+        assert_eq!(v, vec![1, 2]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_swap_remove_out_of_range() {
+        let mut v = vec![1, 2, 3];
+        swap_remove(&mut v, 3);
+    }
+
+    #[test]
+    fn test_longest_run() {
+        let empty: Vec<i32> = vec![];
+        assert_eq!(longest_run(&empty), 0);
+        assert_eq!(longest_run(&[7, 7, 7, 7]), 4);
+        assert_eq!(longest_run(&[1, 2, 1, 2, 1]), 1);
+        assert_eq!(longest_run(&["a", "b", "b", "b", "c", "c"]), 3);
+    }
+
+    #[test]
+    fn test_filter_vec_evens() {
+        assert_eq!(filter_vec(vec![1, 2, 3, 4, 6], |x| x % 2 == 0), vec![2, 4, 6]);
+    }
+
+    #[test]
+    fn test_filter_vec_threshold() {
+        let threshold = 3;
+        assert_eq!(filter_vec(vec![5, 1, 3, 8, 2], |&x| x > threshold), vec![5, 8]);
+        assert_eq!(filter_vec(vec![1, 2], |&x| x > threshold), vec![]);
+    }
+
+    #[test]
+    fn test_map_vec_to_string() {
+        assert_eq!(
+            map_vec(vec![1, -2, 30], |x: i32| x.to_string()),
+            vec!["1", "-2", "30"]
+        );
+    }
+
+    #[test]
+    fn test_map_vec_square() {
+        assert_eq!(map_vec(vec![1, 2, 3, -4], |x| x * x), vec![1, 4, 9, 16]);
+    }
+
+    #[test]
+    fn test_sum() {
+        assert_eq!(sum(&vec![1, 2, 3, -4]), 2);
+    }
+
+    #[test]
+    fn test_reduce_max() {
+        assert_eq!(reduce(&vec![3, 9, -1, 4], i32::MIN, |acc, &x| acc.max(x)), 9);
+    }
+
+    #[test]
+    fn test_reduce_empty() {
+        assert_eq!(reduce(&Vec::new(), 7, |acc, x: &i32| acc + x), 7);
+        assert_eq!(sum(&vec![]), 0);
+    }
+
+    #[test]
+    fn test_concat() {
+        assert_eq!(concat(vec![1, 2], vec![3, 4, 5]), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_concat_empty() {
+        assert_eq!(concat(vec![], vec![3, 4]), vec![3, 4]);
+        assert_eq!(concat(vec!["a"], vec![]), vec!["a"]);
+    }
+
+    #[test]
+    fn test_chunk_exact() {
+        assert_eq!(chunk(&vec![1, 2, 3, 4], 2), vec![vec![1, 2], vec![3, 4]]);
+    }
+
+    #[test]
+    fn test_chunk_remainder() {
+        assert_eq!(chunk(&vec![1, 2, 3, 4, 5], 3), vec![vec![1, 2, 3], vec![4, 5]]);
+        assert_eq!(chunk(&Vec::<i32>::new(), 3), Vec::<Vec<i32>>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunk_size_zero() {
+        chunk(&vec![1, 2, 3], 0);
+    }
+
+    #[test]
+    fn test_flatten() {
+        assert_eq!(flatten(vec![vec![1], vec![2, 3, 4], vec![5, 6]]), vec![1, 2, 3, 4, 5, 6]);
+        assert_eq!(flatten(chunk(&vec![1, 2, 3, 4, 5], 2)), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_flatten_empty_inner() {
+        assert_eq!(flatten(vec![vec![], vec![1, 2], vec![]]), vec![1, 2]);
+        assert_eq!(flatten(vec![Vec::<i32>::new(), vec![]]), Vec::<i32>::new());
+    }
+
+    #[test]
+    fn test_zip() {
+        assert_eq!(zip(&vec![1, 2], &vec!["a", "b"]), vec![(1, "a"), (2, "b")]);
+    }
+
+    #[test]
+    fn test_zip_unequal_lengths() {
+        assert_eq!(zip(&vec![1, 2, 3], &vec!['x']), vec![(1, 'x')]);
+        assert_eq!(zip(&vec![1], &vec![4.0, 5.0]), vec![(1, 4.0)]);
+    }
+
+    #[test]
+    fn test_prefix_sums() {
+        assert_eq!(prefix_sums(&vec![1, 2, 3]), vec![1, 3, 6]);
+        assert_eq!(prefix_sums(&vec![5, -5, 2]), vec![5, 0, 2]);
+    }
+
+    #[test]
+    fn test_prefix_sums_short() {
+        assert_eq!(prefix_sums(&vec![]), vec![]);
+        assert_eq!(prefix_sums(&vec![7]), vec![7]);
+    }
+
+    #[test]
+    fn test_remove_all() {
+        let mut v = vec![1, 2, 1, 3, 1];
+        remove_all(&mut v, 1);
+        assert_eq!(v, vec![2, 3]);
+    }
+
+    #[test]
+    fn test_remove_all_missing() {
+        let mut v = vec![1, 2, 3];
+        remove_all(&mut v, 4);
+        assert_eq!(v, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rotate_left() {
+        let mut v = vec![1, 2, 3, 4];
+        rotate_left(&mut v, 1);
+        assert_eq!(v, vec![2, 3, 4, 1]);
+        rotate_left(&mut v, 0);
+        assert_eq!(v, vec![2, 3, 4, 1]);
+        rotate_left(&mut v, 4);
+        assert_eq!(v, vec![2, 3, 4, 1]);
+        rotate_left(&mut v, 6);
+        assert_eq!(v, vec![4, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_rotate_right() {
+        let mut v = vec![1, 2, 3, 4];
+        rotate_right(&mut v, 1);
+        assert_eq!(v, vec![4, 1, 2, 3]);
+        rotate_right(&mut v, 0);
+        assert_eq!(v, vec![4, 1, 2, 3]);
+        rotate_right(&mut v, 4);
+        assert_eq!(v, vec![4, 1, 2, 3]);
+        rotate_right(&mut v, 7);
+        assert_eq!(v, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_rotate_empty() {
+        let mut v: Vec<i32> = vec![];
+        rotate_left(&mut v, 3);
+        rotate_right(&mut v, 3);
+        assert_eq!(v, vec![]);
+    }
+
+    #[test]
+    fn test_intersperse() {
+        assert_eq!(intersperse(vec![1, 2, 3], 0), vec![1, 0, 2, 0, 3]);
+    }
+
+    #[test]
+    fn test_intersperse_short() {
+        assert_eq!(intersperse(vec![], 0), vec![]);
+        assert_eq!(intersperse(vec![5], 0), vec![5]);
+    }
+
+    #[test]
+    fn test_window_sums() {
+        assert_eq!(window_sums(&vec![1, 2, 3, 4], 2), vec![3, 5, 7]);
+        assert_eq!(window_sums(&vec![1, 2, 3, 4], 4), vec![10]);
+    }
+
+    #[test]
+    fn test_window_sums_bad_width() {
+        assert_eq!(window_sums(&vec![1, 2, 3, 4], 0), vec![]);
+        assert_eq!(window_sums(&vec![1, 2, 3, 4], 5), vec![]);
+    }
+
+    #[test]
+    fn test_partition() {
+        let (evens, odds) = partition(vec![5, 2, 8, 1, 4, 7], |x| x % 2 == 0);
+        assert_eq!(evens, vec![2, 8, 4]);
+        assert_eq!(odds, vec![5, 1, 7]);
+    }
+
+    #[test]
+    fn test_count_occurrences() {
+        let counts = count_occurrences(&vec![3, 1, 3, 3, 2, 1]);
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&3], 3);
+        assert_eq!(counts[&1], 2);
+        assert_eq!(counts[&2], 1);
+    }
+
+    #[test]
+    fn test_count_occurrences_empty() {
+        assert!(count_occurrences(&vec![]).is_empty());
+    }
+
+    #[test]
+    fn test_reverse_inplace() {
+        let mut v = vec![1, 2, 3, 4];
+        reverse_inplace(&mut v);
+        assert_eq!(v, vec![4, 3, 2, 1]);
+
+        let mut v = vec!["a", "b", "c"];
+        reverse_inplace(&mut v);
+        assert_eq!(v, vec!["c", "b", "a"]);
+    }
+
+    #[test]
+    fn test_reverse_inplace_empty() {
+        let mut v: Vec<i32> = vec![];
+        reverse_inplace(&mut v);
+        assert_eq!(v, vec![]);
+    }
+
+    #[test]
+    fn test_find_index() {
+        assert_eq!(find_index(&vec![4, 7, 9, 7], 7), Some(1));
+        assert_eq!(find_index(&vec![4, 7, 9], 5), None);
+        assert_eq!(find_index(&vec![], 5), None);
+    }
+
+    #[test]
+    fn test_group_by_consecutive() {
+        assert_eq!(
+            group_by_consecutive(&vec![1, 1, 2, 3, 3], |a, b| a == b),
+            vec![vec![1, 1], vec![2], vec![3, 3]]
+        );
+        assert_eq!(
+            group_by_consecutive(&vec![1, 3, 2, 4, 5], |a, b| a % 2 == b % 2),
+            vec![vec![1, 3], vec![2, 4], vec![5]]
+        );
+    }
+
+    #[test]
+    fn test_group_by_consecutive_empty() {
+        assert_eq!(group_by_consecutive(&Vec::<i32>::new(), |a, b| a == b), Vec::<Vec<i32>>::new());
+    }
+
+    #[test]
+    fn test_window_max() {
+        assert_eq!(window_max(&vec![1, 3, 2, 5, 4], 2), vec![3, 3, 5, 5]);
+        assert_eq!(window_max(&vec![1, 3, 2, 5, 4], 1), vec![1, 3, 2, 5, 4]);
+        assert_eq!(window_max(&vec![-1, -3, -2], 3), vec![-1]);
+    }
+
+    #[test]
+    fn test_window_max_bad_width() {
+        assert_eq!(window_max(&vec![1, 3, 2], 0), vec![]);
+        assert_eq!(window_max(&vec![1, 3, 2], 4), vec![]);
+    }
 }
@@ -0,0 +1,26 @@
+// Helpers for wrapping text in ANSI color codes. Each takes whether colors are enabled, so they can
+// be switched off (with --no-color) for terminals that don't understand the escape codes.
+
+fn paint(s: &str, code: &str, enabled: bool) -> String {
+    if enabled {
+        format!("\x1B[{}m{}\x1B[0m", code, s)
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn green(s: &str, enabled: bool) -> String {
+    paint(s, "32", enabled)
+}
+
+pub fn bright_green(s: &str, enabled: bool) -> String {
+    paint(s, "92", enabled)
+}
+
+pub fn red(s: &str, enabled: bool) -> String {
+    paint(s, "31", enabled)
+}
+
+pub fn gray(s: &str, enabled: bool) -> String {
+    paint(s, "90", enabled)
+}
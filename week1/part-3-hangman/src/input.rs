@@ -0,0 +1,35 @@
+// Reads the player's input. Input normally comes from stdin, but with --stdin-words stdin holds the
+// word list, so the player's input is read from the terminal (/dev/tty) instead.
+use std::fs::File;
+use std::io::{self, Read};
+
+// Where the player's input comes from.
+#[derive(Clone, Copy)]
+pub enum Source {
+    Stdin,
+    Tty,
+}
+
+// Reads one line from source into buf and returns the number of bytes read, which is 0 at the end
+// of input.
+pub fn read_line(source: Source, buf: &mut String) -> usize {
+    let mut tty = match source {
+        Source::Stdin => return io::stdin().read_line(buf).expect("Error reading line."),
+        Source::Tty => match File::open("/dev/tty") {
+            Ok(tty) => tty,
+            Err(_) => return 0,
+        },
+    };
+    // Reading a byte at a time means nothing after the newline gets consumed, so there's no buffer
+    // to hold on to between calls.
+    let mut line: Vec<u8> = Vec::new();
+    let mut byte = [0u8; 1];
+    while tty.read(&mut byte).expect("Error reading line.") > 0 {
+        line.push(byte[0]);
+        if byte[0] == b'\n' {
+            break;
+        }
+    }
+    buf.push_str(&String::from_utf8_lossy(&line));
+    line.len()
+}
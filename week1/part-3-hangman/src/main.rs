@@ -1,5 +1,5 @@
 // Simple Hangman Program
-// User gets five incorrect guesses
+// User gets five incorrect guesses by default (pass a number as the first argument to change it)
 // Word chosen randomly from words.txt
 // Inspiration from: https://doc.rust-lang.org/book/ch02-00-guessing-game-tutorial.html
 // This assignment will introduce you to some fundamental syntax in Rust:
@@ -13,118 +13,1651 @@
 // We've tried to limit/hide Rust's quirks since we'll discuss those details
 // more in depth in the coming lectures.
 extern crate rand;
-use rand::Rng;
+mod color;
+mod input;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Reverse;
+use std::collections::HashSet;
+use std::env;
 use std::fs;
 use std::io;
-use std::io::Write;
+use std::io::{Read, Write};
+use std::process;
+use std::process::Command;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 
 const NUM_INCORRECT_GUESSES: u32 = 5;
 const WORDS_PATH: &str = "words.txt";
+const DEFAULT_MAX_HINTS: u32 = 2;
+const DEFAULT_PLACEHOLDER: char = '-';
+const SAVE_PATH: &str = "hangman_save.txt";
+const LEADERBOARD_PATH: &str = "scores.txt";
+const LEADERBOARD_SIZE: usize = 5;
 
-fn pick_a_random_word() -> String {
-    let file_string = fs::read_to_string(WORDS_PATH).expect("Unable to read file.");
-    let words: Vec<&str> = file_string.split('\n').collect();
-    String::from(words[rand::thread_rng().gen_range(0, words.len())].trim())
+#[derive(Clone, Copy)]
+enum Difficulty {
+    Easy,
+    Medium,
+    Hard,
 }
 
-fn print_word_so_far(revealed_indexes: &Vec<bool>, secret_word_chars: &Vec<char>) {
+impl Difficulty {
+    // Easy words have at most 5 letters, medium words 6 to 8, and hard words 9 or more.
+    fn allows_word(&self, word: &str) -> bool {
+        let len = word.chars().count();
+        match *self {
+            Difficulty::Easy => len <= 5,
+            Difficulty::Medium => (6..=8).contains(&len),
+            Difficulty::Hard => len >= 9,
+        }
+    }
+
+    fn num_incorrect_guesses(&self) -> u32 {
+        match *self {
+            Difficulty::Easy => 8,
+            Difficulty::Medium => NUM_INCORRECT_GUESSES,
+            Difficulty::Hard => 4,
+        }
+    }
+}
+
+// Returns None if input runs out before a difficulty is chosen.
+fn get_difficulty(source: input::Source) -> Option<Difficulty> {
+    loop {
+        print!("Choose a difficulty (easy/medium/hard): ");
+        io::stdout().flush().expect("Error flushing stdout.");
+
+        let mut choice = String::new();
+        let num_bytes = input::read_line(source, &mut choice);
+        if num_bytes == 0 {
+            println!();
+            return None;
+        }
+
+        match choice.trim().to_lowercase().as_str() {
+            "easy" | "e" => return Some(Difficulty::Easy),
+            "medium" | "m" => return Some(Difficulty::Medium),
+            "hard" | "h" => return Some(Difficulty::Hard),
+            _ => println!("Please enter easy, medium, or hard"),
+        }
+    }
+}
+
+// Word categories offered at startup, along with the file each one's words are read from.
+const CATEGORIES: [(&str, &str); 3] = [
+    ("general", WORDS_PATH),
+    ("animals", "animals.txt"),
+    ("countries", "countries.txt"),
+];
+
+// Returns None if input runs out before a category is chosen.
+fn get_category(source: input::Source) -> Option<(&'static str, &'static str)> {
+    println!("Categories:");
+    for (i, (name, _path)) in CATEGORIES.iter().enumerate() {
+        println!("  {}) {}", i + 1, name);
+    }
+    loop {
+        print!("Choose a category: ");
+        io::stdout().flush().expect("Error flushing stdout.");
+
+        let mut choice = String::new();
+        let num_bytes = input::read_line(source, &mut choice);
+        if num_bytes == 0 {
+            println!();
+            return None;
+        }
+
+        match choice.trim().parse::<usize>() {
+            Ok(num) if (1..=CATEGORIES.len()).contains(&num) => return Some(CATEGORIES[num - 1]),
+            _ => println!("Please enter a number between 1 and {}", CATEGORIES.len()),
+        }
+    }
+}
+
+// A word from the word list, along with how often it should be picked relative to the others.
+struct WeightedWord {
+    text: String,
+    weight: usize,
+}
+
+// Parses one line of a words file. A line can end with a weight, like "apple 3", to make the word
+// come up three times as often; without one the weight is 1. Only a last part that starts like a
+// number counts as a weight, so a phrase like "new york" is still read as a whole.
+fn parse_word_line(line: &str) -> WeightedWord {
+    let mut parts = line.rsplitn(2, char::is_whitespace);
+    let last = parts.next().unwrap_or("");
+    if let Some(text) = parts.next() {
+        if last.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+') {
+            let weight = match last.parse::<usize>() {
+                Ok(weight) if weight > 0 => weight,
+                _ => {
+                    println!("Warning: \"{}\" has an invalid weight, using 1.", line);
+                    1
+                }
+            };
+            return WeightedWord {
+                text: text.trim().to_string(),
+                weight,
+            };
+        }
+    }
+    WeightedWord {
+        text: line.to_string(),
+        weight: 1,
+    }
+}
+
+// Splits the contents of a words file into one word per line, skipping blank lines (including the
+// empty one after a trailing newline).
+fn parse_words(file_string: &str) -> Vec<WeightedWord> {
+    file_string
+        .split('\n')
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(parse_word_line)
+        .collect()
+}
+
+// Reads the word list piped in with --stdin-words. Exits with an error if there aren't any words.
+fn read_stdin_words() -> Vec<WeightedWord> {
+    let mut file_string = String::new();
+    if let Err(err) = io::stdin().read_to_string(&mut file_string) {
+        println!("Unable to read words from stdin: {}", err);
+        process::exit(1);
+    }
+    let words = parse_words(&file_string);
+    if words.is_empty() {
+        println!("No words were given on stdin");
+        process::exit(1);
+    }
+    words
+}
+
+// Reads the words in the words file at path, falling back to the default words file if it can't be
+// read. Exits with an error naming the file if the default can't be read either, or if the file
+// that was read has no words in it.
+fn read_words_file(path: &str) -> Vec<WeightedWord> {
+    match fs::read_to_string(path) {
+        Ok(file_string) => {
+            let words = parse_words(&file_string);
+            if words.is_empty() {
+                println!("Words file {} doesn't contain any words", path);
+                process::exit(1);
+            }
+            words
+        }
+        Err(err) if path != WORDS_PATH => {
+            println!(
+                "Unable to read {}: {}. Using {} instead.",
+                path, err, WORDS_PATH
+            );
+            read_words_file(WORDS_PATH)
+        }
+        Err(err) => {
+            println!("Unable to read words file {}: {}", path, err);
+            process::exit(1);
+        }
+    }
+}
+
+// Picks a random word of the given difficulty that isn't in used_words. Once every such word has
+// been used, they're all removed from used_words so the cycle can start over.
+fn pick_a_random_word(
+    words: &[WeightedWord],
+    level: Difficulty,
+    used_words: &mut HashSet<String>,
+) -> String {
+    let mut candidates: Vec<&WeightedWord> = words
+        .iter()
+        .filter(|word| level.allows_word(&word.text))
+        .collect();
+    if candidates.is_empty() {
+        println!("No words match that difficulty, so picking from the full word list.");
+        candidates = words.iter().collect();
+    }
+    let unused: Vec<&WeightedWord> = candidates
+        .iter()
+        .cloned()
+        .filter(|word| !used_words.contains(&word.text))
+        .collect();
+    if unused.is_empty() {
+        println!("You've played every word, so the words will start repeating.");
+        for word in candidates.iter() {
+            used_words.remove(&word.text);
+        }
+    } else {
+        candidates = unused;
+    }
+    pick_weighted(&candidates, &mut rand::thread_rng())
+}
+
+// Picks one of candidates at random, with each word's chance of being picked proportional to its
+// weight.
+fn pick_weighted<R: Rng>(candidates: &[&WeightedWord], rng: &mut R) -> String {
+    let total_weight: usize = candidates.iter().map(|word| word.weight).sum();
+    let mut target = rng.gen_range(0, total_weight);
+    for word in candidates {
+        if target < word.weight {
+            return word.text.clone();
+        }
+        target -= word.weight;
+    }
+    unreachable!("target is always less than the total weight");
+}
+
+fn pick_a_random_word_by_difficulty(
+    path: &str,
+    level: Difficulty,
+    used_words: &mut HashSet<String>,
+) -> String {
+    pick_a_random_word(&read_words_file(path), level, used_words)
+}
+
+fn print_word_so_far(
+    revealed_indexes: &[bool],
+    secret_word_chars: &[char],
+    placeholder: char,
+    use_color: bool,
+) {
     let mut s: String = String::new();
     for i in 0..secret_word_chars.len() {
-        if revealed_indexes[i] {
-            s.push(secret_word_chars[i]);
+        if secret_word_chars[i] == ' ' {
+            s.push(' ');
+        } else if revealed_indexes[i] {
+            s.push_str(&color::green(&secret_word_chars[i].to_string(), use_color));
         } else {
-            s.push('-');
+            s.push_str(&color::gray(&placeholder.to_string(), use_color));
         }
     }
 
     println!("The word so far is {}", s);
 }
 
-fn print_guessed_so_far(guessed_so_far: &Vec<char>) {
-    let mut s: String = String::new();
-    for i in 0..guessed_so_far.len() {
-        s.push(guessed_so_far[i]);
-    }
-    println!("You have guessed the following letters: {}", s);
+const NUM_BODY_PARTS: u32 = 6;
+
+// The number of body parts to draw once used out of total guesses are gone. Parts are added in
+// proportion to the guesses used, so the figure is complete once the last guess is gone no matter
+// how many guesses the player started with.
+fn num_body_parts(used: u32, total: u32) -> u32 {
+    (used.min(total) * NUM_BODY_PARTS)
+        .checked_div(total)
+        .unwrap_or(0)
+}
+
+fn draw_gallows(used: u32, total: u32) -> String {
+    let num_parts = num_body_parts(used, total);
+    let part = |i: u32, s: &'static str| if num_parts > i { s } else { " " };
+
+    let mut s = String::new();
+    s.push_str("  +---+\n");
+    s.push_str("  |   |\n");
+    s.push_str(&format!("  {}   |\n", part(0, "O")));
+    s.push_str(&format!(
+        " {}{}{}  |\n",
+        part(2, "/"),
+        part(1, "|"),
+        part(3, "\\")
+    ));
+    s.push_str(&format!(" {} {}  |\n", part(4, "/"), part(5, "\\")));
+    s.push_str("      |\n");
+    s.push_str("=========");
+    s
+}
+
+fn print_gallows(used: u32, total: u32) {
+    println!("{}", draw_gallows(used, total));
+}
+
+// Lists each letter once, in alphabetical order, no matter how or in what order it was guessed.
+fn sorted_letters(letters: &[char]) -> String {
+    let mut sorted: Vec<char> = letters.iter().flat_map(|c| c.to_lowercase()).collect();
+    sorted.sort();
+    sorted.dedup();
+    sorted.into_iter().collect()
+}
+
+fn print_guessed_so_far(guessed_so_far: &[char], wrong_guesses: &[char], use_color: bool) {
+    println!("Guessed: {}", sorted_letters(guessed_so_far));
+    println!(
+        "Wrong: {}",
+        color::red(&sorted_letters(wrong_guesses), use_color)
+    );
 }
 
 fn print_num_guess_left(num_guess_left: u32) {
     println!("You have {} guesses left", num_guess_left);
 }
 
-fn get_guess() -> char {
-    print!("Please guess a letter: ");
-    io::stdout().flush().expect("Error flushing stdout.");
+enum Guess {
+    Letter(char),
+    Word(String),
+    Hint,
+    Stats,
+    Undo,
+    Save,
+}
+
+// A word is made of letters; a phrase is several words separated by spaces, like "new york".
+fn is_word_or_phrase(chars: &[char]) -> bool {
+    chars.iter().any(|c| c.is_alphabetic()) && chars.iter().all(|&c| c.is_alphabetic() || c == ' ')
+}
+
+// Keeps prompting until the user enters either a single letter or a whole word or phrase (anything
+// longer than one character; see is_word_or_phrase). Returns None if input runs out, e.g. on Ctrl-D
+// or at the end of a piped file.
+fn get_guess(source: input::Source) -> Option<Guess> {
+    loop {
+        print!("Guess a letter (or the whole word, \"hint\", \"stats\", \"undo\", or \"save\"): ");
+        io::stdout().flush().expect("Error flushing stdout.");
+
+        let mut guess = String::new();
+        let num_bytes = input::read_line(source, &mut guess);
+        if num_bytes == 0 {
+            println!();
+            return None;
+        }
+
+        if guess.trim().eq_ignore_ascii_case("hint") {
+            return Some(Guess::Hint);
+        }
+        if guess.trim().eq_ignore_ascii_case("stats") {
+            return Some(Guess::Stats);
+        }
+        if guess.trim().eq_ignore_ascii_case("undo") {
+            return Some(Guess::Undo);
+        }
+        if guess.trim().eq_ignore_ascii_case("save") {
+            return Some(Guess::Save);
+        }
+        let guess_chars: Vec<char> = guess.trim().chars().collect();
+        if is_word_or_phrase(&guess_chars) {
+            if guess_chars.len() == 1 {
+                return Some(Guess::Letter(guess_chars[0]));
+            }
+            return Some(Guess::Word(guess_chars.into_iter().collect()));
+        }
+        println!("Please enter a single letter or a whole word");
+    }
+}
 
-    let mut guess = String::new();
-    io::stdin()
-        .read_line(&mut guess)
-        .expect("Error reading line.");
-    guess.chars().next().unwrap()
+// Letters match regardless of case, so guessing 'A' reveals 'a' and vice versa.
+fn same_letter(a: char, b: char) -> bool {
+    a.to_lowercase().eq(b.to_lowercase())
 }
 
-fn process_user_input(
-    revealed_indexes: &mut Vec<bool>,
-    secret_word_chars: &Vec<char>,
-    c: char,
+// Maps a lowercase accented Latin letter to the letter it's based on, e.g. 'é' to 'e'. Any other
+// character is returned unchanged.
+fn strip_accent(c: char) -> char {
+    match c {
+        'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ā' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'è' | 'é' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => 'e',
+        'ì' | 'í' | 'î' | 'ï' | 'ī' => 'i',
+        'ñ' | 'ń' | 'ň' => 'n',
+        'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' | 'ō' => 'o',
+        'ś' | 'š' => 's',
+        'ù' | 'ú' | 'û' | 'ü' | 'ū' | 'ů' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        _ => c,
+    }
+}
+
+// Like same_letter, but an accented letter also matches its base letter, so guessing 'e' reveals
+// 'é' and 'è'.
+fn same_base_letter(a: char, b: char) -> bool {
+    a.to_lowercase()
+        .map(strip_accent)
+        .eq(b.to_lowercase().map(strip_accent))
+}
+
+#[derive(Debug, PartialEq)]
+enum GuessResult {
+    Correct,
+    Incorrect,
+    AlreadyGuessed,
+}
+
+// The letters of the word in lowercase, leaving out anything that isn't a letter, like the space in
+// a phrase.
+fn word_letters(secret_word_chars: &[char]) -> Vec<char> {
+    secret_word_chars
+        .iter()
+        .filter(|c| c.is_alphabetic())
+        .flat_map(|c| c.to_lowercase())
+        .collect()
+}
+
+#[derive(Debug, PartialEq)]
+enum HintResult {
+    // The index of the letter that was revealed.
+    Revealed(usize),
+    // At most one letter is still hidden, so a hint would give the whole word away.
+    TooFewHidden,
+    // Paying for the hint would use up the last guess and lose the game.
+    LastGuess,
+}
+
+// Returns how many vowels the word has (counting repeats) and how many distinct letters it has.
+fn describe_word(secret_word_chars: &[char]) -> (usize, usize) {
+    let letters = word_letters(secret_word_chars);
+    let num_vowels = letters
+        .iter()
+        .filter(|&&c| "aeiou".contains(strip_accent(c)))
+        .count();
+    let distinct_letters: HashSet<&char> = letters.iter().collect();
+    (num_vowels, distinct_letters.len())
+}
+
+// What a single letter or word guess changed, so that it can be undone.
+struct GuessRecord {
+    // The letter that was guessed, or None for a word guess.
+    letter: Option<char>,
+    // The indexes that this guess revealed and that weren't already revealed.
+    newly_revealed: Vec<usize>,
+    correct: bool,
+}
+
+// The state of a single game. It does no input or output of its own, so a whole game can be
+// played by calling its methods directly.
+struct Game {
+    // Note: given what you know about Rust so far, it's easier to pull characters out of a
+    // vector than it is to pull them out of a string. You can get the ith character of
+    // the secret word by doing secret_word_chars[i].
+    secret_word_chars: Vec<char>,
+    revealed_indexes: Vec<bool>,
+    guessed_so_far: Vec<char>,
+    // The guessed letters that weren't in the word, in the order they were guessed.
+    wrong_guesses: Vec<char>,
+    num_guesses: u32,
     num_guess_left: u32,
-) -> u32 {
-    let mut found: bool = false;
-    let mut returnv = num_guess_left;
+    num_hints_used: u32,
+    // Whether accented letters match their base letters (see same_base_letter).
+    ignore_accents: bool,
+    // Whether the player has already asked for the word's stats this game.
+    described: bool,
+    // The guesses made so far, most recent last, for undo. Hints aren't recorded, so they can't be
+    // undone.
+    history: Vec<GuessRecord>,
+}
 
-    for i in 0..secret_word_chars.len() {
-        if secret_word_chars[i] == c {
-            revealed_indexes[i] = true;
-            found = true;
+impl Game {
+    fn new(secret_word: &str, num_guesses: u32) -> Game {
+        let secret_word_chars: Vec<char> = secret_word.chars().collect();
+        // One position per char rather than per byte, so a multi-byte letter like 'é' is
+        // revealed as a single letter. The spaces in a phrase like "new york" start out revealed,
+        // since they never need to be guessed.
+        let revealed_indexes: Vec<bool> = secret_word_chars.iter().map(|&c| c == ' ').collect();
+        Game {
+            secret_word_chars,
+            revealed_indexes,
+            guessed_so_far: Vec::new(),
+            wrong_guesses: Vec::new(),
+            num_guesses,
+            num_guess_left: num_guesses,
+            num_hints_used: 0,
+            ignore_accents: false,
+            described: false,
+            history: Vec::new(),
+        }
+    }
+
+    fn secret_word(&self) -> String {
+        self.secret_word_chars.iter().collect()
+    }
+
+    fn matches(&self, a: char, b: char) -> bool {
+        if self.ignore_accents {
+            same_base_letter(a, b)
+        } else {
+            same_letter(a, b)
+        }
+    }
+
+    // Reveals every occurrence of c. A letter that isn't in the word costs a guess, but guessing
+    // the same letter twice costs nothing.
+    fn guess(&mut self, c: char) -> GuessResult {
+        if self
+            .guessed_so_far
+            .iter()
+            .any(|&guessed| self.matches(guessed, c))
+        {
+            return GuessResult::AlreadyGuessed;
+        }
+        self.guessed_so_far.push(c);
+
+        let mut found: bool = false;
+        let mut newly_revealed: Vec<usize> = Vec::new();
+        for i in 0..self.secret_word_chars.len() {
+            if self.matches(self.secret_word_chars[i], c) {
+                if !self.revealed_indexes[i] {
+                    newly_revealed.push(i);
+                }
+                self.revealed_indexes[i] = true;
+                found = true;
+            }
+        }
+        self.history.push(GuessRecord {
+            letter: Some(c),
+            newly_revealed,
+            correct: found,
+        });
+
+        if found {
+            GuessResult::Correct
+        } else {
+            self.wrong_guesses.push(c);
+            self.num_guess_left -= 1;
+            GuessResult::Incorrect
+        }
+    }
+
+    // Guesses each letter in letters in turn, stopping early if the game ends partway through.
+    // Spaces are skipped. Returns the result for each letter that was guessed.
+    fn guess_letters(&mut self, letters: &str) -> Vec<(char, GuessResult)> {
+        let mut results: Vec<(char, GuessResult)> = Vec::new();
+        for c in letters.chars().filter(|&c| c != ' ') {
+            if self.is_won() || self.is_lost() {
+                break;
+            }
+            results.push((c, self.guess(c)));
+        }
+        results
+    }
+
+    // A correct word guess reveals the whole word; a wrong one costs a guess like a wrong letter.
+    fn guess_word(&mut self, word: &str) -> GuessResult {
+        let word_chars: Vec<char> = word.chars().collect();
+        let correct = word_chars.len() == self.secret_word_chars.len()
+            && word_chars
+                .iter()
+                .zip(self.secret_word_chars.iter())
+                .all(|(&a, &b)| self.matches(a, b));
+
+        let newly_revealed: Vec<usize> = if correct {
+            (0..self.revealed_indexes.len())
+                .filter(|&i| !self.revealed_indexes[i])
+                .collect()
+        } else {
+            Vec::new()
+        };
+        self.history.push(GuessRecord {
+            letter: None,
+            newly_revealed,
+            correct,
+        });
+
+        if correct {
+            for revealed in self.revealed_indexes.iter_mut() {
+                *revealed = true;
+            }
+            GuessResult::Correct
+        } else {
+            self.num_guess_left -= 1;
+            GuessResult::Incorrect
+        }
+    }
+
+    // Reveals one random hidden letter at the cost of a guess. The hint is refused, without being
+    // used up, if at most one letter is still hidden or if only one guess is left.
+    fn hint(&mut self) -> HintResult {
+        let hidden: Vec<usize> = (0..self.revealed_indexes.len())
+            .filter(|&i| !self.revealed_indexes[i])
+            .collect();
+        if hidden.len() <= 1 {
+            return HintResult::TooFewHidden;
+        }
+        if self.num_guess_left <= 1 {
+            return HintResult::LastGuess;
+        }
+
+        let index = hidden[rand::thread_rng().gen_range(0, hidden.len())];
+        self.revealed_indexes[index] = true;
+        self.num_guess_left -= 1;
+        self.num_hints_used += 1;
+        HintResult::Revealed(index)
+    }
+
+    // Returns the word's vowel and distinct letter counts (see describe_word). This is free, but
+    // only works once per game; after that it returns None.
+    fn describe(&mut self) -> Option<(usize, usize)> {
+        if self.described {
+            return None;
+        }
+        self.described = true;
+        Some(describe_word(&self.secret_word_chars))
+    }
+
+    // Takes back the most recent letter or word guess, hiding the letters it revealed and refunding
+    // the guess it cost. Returns false if there's no guess left to undo.
+    fn undo(&mut self) -> bool {
+        let record = match self.history.pop() {
+            Some(record) => record,
+            None => return false,
+        };
+        for i in record.newly_revealed {
+            self.revealed_indexes[i] = false;
+        }
+        if record.letter.is_some() {
+            self.guessed_so_far.pop();
         }
+        if !record.correct {
+            if record.letter.is_some() {
+                self.wrong_guesses.pop();
+            }
+            self.num_guess_left += 1;
+        }
+        true
+    }
+
+    // Ends the game as a loss, e.g. because the time limit ran out.
+    fn forfeit(&mut self) {
+        self.num_guess_left = 0;
+    }
+
+    fn is_won(&self) -> bool {
+        self.revealed_indexes.iter().all(|&revealed| revealed)
     }
 
-    if !found {
-        returnv -= 1;
-        println!("Sorry, that letter is not in the word");
+    fn is_lost(&self) -> bool {
+        self.num_guess_left == 0
     }
-    returnv
 }
 
-fn print_result(num_guess_left: u32, secret_word: &String) {
-    if num_guess_left == 0 {
+// Prints how many letter guesses were made and how many of them were in the word. Each letter
+// counts once no matter how many times it appears in the word.
+fn print_stats(game: &Game) {
+    let num_guesses = game.guessed_so_far.len();
+    let num_correct = game
+        .guessed_so_far
+        .iter()
+        .filter(|&&guessed| {
+            game.secret_word_chars
+                .iter()
+                .any(|&c| game.matches(c, guessed))
+        })
+        .count();
+    println!("Letters guessed: {}", num_guesses);
+    println!(
+        "Correct: {}, incorrect: {}",
+        num_correct,
+        num_guesses - num_correct
+    );
+    if num_guesses > 0 {
+        println!(
+            "Accuracy: {:.1}%",
+            num_correct as f64 * 100.0 / num_guesses as f64
+        );
+    }
+}
+
+fn print_result(game: &Game, use_color: bool) {
+    if game.is_lost() {
         println!("Sorry, you ran out of guesses!");
     } else {
         println!(
-            "Congratulations you guessed the secret word: {}",
-            secret_word
+            "{}",
+            color::bright_green(
+                &format!(
+                    "Congratulations you guessed the secret word: {}",
+                    game.secret_word()
+                ),
+                use_color
+            )
         );
     }
+    print_stats(game);
 }
 
-fn main() {
-    let secret_word = pick_a_random_word();
-    // Note: given what you know about Rust so far, it's easier to pull characters out of a
-    // vector than it is to pull them out of a string. You can get the ith character of
-    // secret_word by doing secret_word_chars[i].
-    let secret_word_chars: Vec<char> = secret_word.chars().collect();
+// Longer words and words with more distinct letters are harder to guess, since every distinct
+// letter needs its own correct guess.
+fn word_difficulty(secret_word_chars: &[char]) -> usize {
+    let (_, num_distinct_letters) = describe_word(secret_word_chars);
+    word_letters(secret_word_chars).len() + 2 * num_distinct_letters
+}
+
+fn print_word_difficulty(secret_word_chars: &[char]) {
+    println!(
+        "Difficulty rating for this word: {}",
+        word_difficulty(secret_word_chars)
+    );
+}
+
+// Turns terminal echo on or off. Returns false if that isn't possible, e.g. because stdin isn't a
+// terminal.
+fn set_echo(enabled: bool) -> bool {
+    Command::new("stty")
+        .arg(if enabled { "echo" } else { "-echo" })
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+// Has player one type in the secret word for two-player mode. Typing is hidden when the terminal
+// allows it; otherwise the screen is cleared once the word is entered. Returns None if input runs
+// out before a word is entered.
+fn get_secret_word_from_player(source: input::Source) -> Option<String> {
+    loop {
+        print!("Player one, enter the secret word: ");
+        io::stdout().flush().expect("Error flushing stdout.");
+
+        let hidden = set_echo(false);
+        let mut word = String::new();
+        let num_bytes = input::read_line(source, &mut word);
+        if hidden {
+            set_echo(true);
+            println!();
+        }
+        if num_bytes == 0 {
+            if !hidden {
+                println!();
+            }
+            return None;
+        }
+
+        let word = word.trim();
+        if !is_word_or_phrase(&word.chars().collect::<Vec<char>>()) {
+            println!("The secret word must only contain letters and spaces");
+            continue;
+        }
+        if !hidden {
+            // Clear the screen so player two can't see the word.
+            print!("\x1B[2J\x1B[1;1H");
+        }
+        println!("Player two, start guessing!");
+        return Some(word.to_string());
+    }
+}
+
+// Everything needed to pick a game back up where it was left off.
+struct SavedGame {
+    category: String,
+    game: Game,
+}
+
+// Writes the game to path, one field per line.
+fn save_game(path: &str, category: &str, game: &Game) -> io::Result<()> {
+    let revealed: String = game
+        .revealed_indexes
+        .iter()
+        .map(|&revealed| if revealed { '1' } else { '0' })
+        .collect();
+    let guessed: String = game.guessed_so_far.iter().collect();
+    let contents = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{}\n",
+        category,
+        game.secret_word(),
+        revealed,
+        guessed,
+        game.num_guesses,
+        game.num_guess_left,
+        game.num_hints_used
+    );
+    fs::write(path, contents)
+}
+
+fn parse_saved_game(contents: &str) -> Option<SavedGame> {
+    let lines: Vec<&str> = contents.lines().collect();
+    if lines.len() != 7 {
+        return None;
+    }
+    let secret_word_chars: Vec<char> = lines[1].chars().collect();
     let mut revealed_indexes: Vec<bool> = Vec::new();
-    let mut guessed_so_far: Vec<char> = Vec::new();
-    let mut num_guess_left = NUM_INCORRECT_GUESSES;
+    for c in lines[2].chars() {
+        match c {
+            '0' => revealed_indexes.push(false),
+            '1' => revealed_indexes.push(true),
+            _ => return None,
+        }
+    }
+    let num_guesses: u32 = lines[4].parse().ok()?;
+    let num_guess_left: u32 = lines[5].parse().ok()?;
+    if secret_word_chars.is_empty()
+        || revealed_indexes.len() != secret_word_chars.len()
+        || num_guess_left > num_guesses
+    {
+        return None;
+    }
+    let guessed_so_far: Vec<char> = lines[3].chars().collect();
+    // The wrong guesses aren't saved separately; they're whichever guessed letters aren't in the
+    // word.
+    let wrong_guesses: Vec<char> = guessed_so_far
+        .iter()
+        .cloned()
+        .filter(|&guessed| !secret_word_chars.iter().any(|&c| same_letter(c, guessed)))
+        .collect();
+    Some(SavedGame {
+        category: lines[0].to_string(),
+        game: Game {
+            secret_word_chars,
+            revealed_indexes,
+            guessed_so_far,
+            wrong_guesses,
+            num_guesses,
+            num_guess_left,
+            num_hints_used: lines[6].parse().ok()?,
+            ignore_accents: false,
+            described: false,
+            history: Vec::new(),
+        },
+    })
+}
 
-    for _i in 0..secret_word.len() {
-        revealed_indexes.push(false);
+// Reads a game written by save_game. Returns None (after saying why) if the file is missing or
+// doesn't hold a valid game.
+fn load_game(path: &str) -> Option<SavedGame> {
+    let contents = match fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) => {
+            println!(
+                "Unable to read saved game {}: {}. Starting a new game.",
+                path, err
+            );
+            return None;
+        }
+    };
+    let game = parse_saved_game(&contents);
+    if game.is_none() {
+        println!("Saved game {} is corrupt. Starting a new game.", path);
     }
+    game
+}
 
-    println!("Welcome to CS110L Hangman!");
+// Command line options.
+// Usage: hangman [num_guesses] [--words path | --stdin-words] [--time-limit seconds] [--hints n]
+//                [--placeholder c] [--resume] [--two-player] [--daily] [--ignore-accents]
+//                [--multi-letter] [--no-color] [--bell]
+//
+// --multi-letter treats anything longer than one letter, like "aeiou", as a guess of each of its
+// letters in turn rather than a guess of the whole word.
+//
+// --stdin-words reads the word list from stdin, e.g. `cat words.txt | hangman --stdin-words`. Since
+// stdin is then used up, guesses are read from the terminal, so this needs to be run from one.
+struct Options {
+    words_path: Option<String>,
+    stdin_words: Option<Vec<WeightedWord>>,
+    num_guesses: Option<u32>,
+    time_limit: Option<Duration>,
+    // The most hints allowed per game.
+    max_hints: u32,
+    resume: bool,
+    two_player: bool,
+    daily: bool,
+    ignore_accents: bool,
+    // Whether to ring the terminal bell on a wrong guess.
+    bell: bool,
+    // Shown in place of each letter that hasn't been guessed yet.
+    placeholder: char,
+    multi_letter: bool,
+    // Whether to color the output with ANSI escape codes.
+    color: bool,
+    // Where to read the player's input from.
+    input: input::Source,
+}
+
+// A placeholder has to be a single character, and can't be a letter or a space since those would be
+// mistaken for part of the word.
+fn parse_placeholder(arg: &str) -> Option<char> {
+    let mut chars = arg.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if !c.is_alphabetic() && !c.is_whitespace() => Some(c),
+        _ => None,
+    }
+}
+
+fn parse_args() -> Options {
+    let mut options = Options {
+        words_path: None,
+        stdin_words: None,
+        num_guesses: None,
+        time_limit: None,
+        max_hints: DEFAULT_MAX_HINTS,
+        resume: false,
+        two_player: false,
+        daily: false,
+        ignore_accents: false,
+        bell: false,
+        placeholder: DEFAULT_PLACEHOLDER,
+        multi_letter: false,
+        color: true,
+        input: input::Source::Stdin,
+    };
+    let mut stdin_words = false;
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--words" => match args.next() {
+                Some(path) => options.words_path = Some(path),
+                None => println!("Warning: --words needs a file path, ignoring it."),
+            },
+            "--stdin-words" => stdin_words = true,
+            "--resume" => options.resume = true,
+            "--two-player" => options.two_player = true,
+            "--daily" => options.daily = true,
+            "--ignore-accents" => options.ignore_accents = true,
+            "--no-color" => options.color = false,
+            "--bell" => options.bell = true,
+            "--multi-letter" => options.multi_letter = true,
+            "--placeholder" => match args.next().as_ref().and_then(|arg| parse_placeholder(arg)) {
+                Some(placeholder) => options.placeholder = placeholder,
+                None => println!(
+                    "Warning: --placeholder needs a single non-letter character, using '{}'.",
+                    DEFAULT_PLACEHOLDER
+                ),
+            },
+            "--time-limit" => match args.next().and_then(|secs| secs.parse::<u64>().ok()) {
+                Some(secs) if secs > 0 => options.time_limit = Some(Duration::from_secs(secs)),
+                _ => println!("Warning: --time-limit needs a number of seconds, ignoring it."),
+            },
+            "--hints" => match args.next().and_then(|hints| hints.parse::<u32>().ok()) {
+                Some(max_hints) => options.max_hints = max_hints,
+                None => println!(
+                    "Warning: --hints needs a number of hints, using the default of {}.",
+                    DEFAULT_MAX_HINTS
+                ),
+            },
+            _ => match arg.parse::<u32>() {
+                Ok(num_guesses) if num_guesses > 0 => options.num_guesses = Some(num_guesses),
+                _ => println!(
+                    "Warning: \"{}\" is not a valid number of guesses, using the default.",
+                    arg
+                ),
+            },
+        }
+    }
+    if stdin_words {
+        options.stdin_words = Some(read_stdin_words());
+        options.input = input::Source::Tty;
+    }
+    options
+}
+
+// Keeps track of results across all the games played in one run of the program.
+struct Session {
+    wins: u32,
+    losses: u32,
+    score: u32,
+    // Games won in a row, and the most ever won in a row this session.
+    streak: u32,
+    best_streak: u32,
+    // Words already played this session, so they aren't picked again until every word has been.
+    used_words: HashSet<String>,
+}
+
+impl Session {
+    fn new() -> Session {
+        Session {
+            wins: 0,
+            losses: 0,
+            score: 0,
+            streak: 0,
+            best_streak: 0,
+            used_words: HashSet::new(),
+        }
+    }
+
+    // A win is worth 10 points for every guess the player had left.
+    fn record_game(&mut self, num_guess_left: u32) {
+        if num_guess_left > 0 {
+            self.wins += 1;
+            self.score += num_guess_left * 10;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+        } else {
+            self.losses += 1;
+            self.streak = 0;
+        }
+    }
+
+    fn print_running_total(&self) {
+        println!(
+            "Score: {} ({} wins, {} losses)",
+            self.score, self.wins, self.losses
+        );
+        println!("Win streak: {}", self.streak);
+    }
+
+    fn print_summary(&self) {
+        println!("Thanks for playing!");
+        println!(
+            "You played {} games: {} wins and {} losses.",
+            self.wins + self.losses,
+            self.wins,
+            self.losses
+        );
+        println!("Final score: {}", self.score);
+        println!("Best streak: {}", self.best_streak);
+    }
+}
+
+// Asks whether to start another game. Running out of input counts as a no, so piping in a fixed
+// set of guesses ends the session cleanly.
+fn ask_play_again(source: input::Source) -> bool {
+    loop {
+        print!("Play again? (y/n): ");
+        io::stdout().flush().expect("Error flushing stdout.");
+
+        let mut answer = String::new();
+        let num_bytes = input::read_line(source, &mut answer);
+        if num_bytes == 0 {
+            println!();
+            return false;
+        }
+
+        match answer.trim().to_lowercase().as_str() {
+            "y" | "yes" => return true,
+            "n" | "no" => return false,
+            _ => println!("Please enter y or n"),
+        }
+    }
+}
+
+// Picks the word of the day from the words file at path. The RNG is seeded with the number of days
+// since the Unix epoch (in UTC), so everyone gets the same word on the same day.
+fn pick_daily_word(path: &str) -> String {
+    let words = read_words_file(path);
+    let days_since_epoch = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System clock is set before 1970.")
+        .as_secs()
+        / (24 * 60 * 60);
+    let mut rng = StdRng::seed_from_u64(days_since_epoch);
+    let candidates: Vec<&WeightedWord> = words.iter().collect();
+    pick_weighted(&candidates, &mut rng)
+}
+
+// Starts a fresh game. In two-player mode player one supplies the word; otherwise this asks for a
+// category and difficulty and picks a random word. Returns None if input runs out partway through.
+fn new_game(options: &Options, used_words: &mut HashSet<String>) -> Option<SavedGame> {
+    let (category, secret_word, default_num_guesses) = if options.two_player {
+        (
+            "two-player",
+            get_secret_word_from_player(options.input)?,
+            NUM_INCORRECT_GUESSES,
+        )
+    } else if options.daily {
+        let words_path = options
+            .words_path
+            .as_ref()
+            .map_or(WORDS_PATH, |path| path.as_str());
+        ("daily", pick_daily_word(words_path), NUM_INCORRECT_GUESSES)
+    } else if let Some(words) = &options.stdin_words {
+        let difficulty = get_difficulty(options.input)?;
+        (
+            "stdin",
+            pick_a_random_word(words, difficulty, used_words),
+            difficulty.num_incorrect_guesses(),
+        )
+    } else {
+        // A words file given on the command line replaces the category menu.
+        let (category, words_path) = match &options.words_path {
+            Some(path) => ("custom", path.as_str()),
+            None => get_category(options.input)?,
+        };
+        let difficulty = get_difficulty(options.input)?;
+        (
+            category,
+            pick_a_random_word_by_difficulty(words_path, difficulty, used_words),
+            difficulty.num_incorrect_guesses(),
+        )
+    };
+    let num_guesses = options.num_guesses.unwrap_or(default_num_guesses);
+
+    Some(SavedGame {
+        category: category.to_string(),
+        game: Game::new(&secret_word, num_guesses),
+    })
+}
+
+// Rings the terminal bell if --bell was given. It's printed without a newline, so it goes out along
+// with the next line of output.
+fn ring_bell(options: &Options) {
+    if options.bell {
+        print!("\x07");
+    }
+}
+
+// Plays a single game, either resuming saved_game or starting a new one, and returns the number of
+// guesses left when it ended (0 means a loss). Returns None if the player quit partway through.
+// The word played is added to used_words so later games in the session can avoid it.
+fn play_game(
+    options: &Options,
+    saved_game: Option<SavedGame>,
+    used_words: &mut HashSet<String>,
+) -> Option<u32> {
+    let SavedGame { category, mut game } = match saved_game {
+        Some(saved_game) => saved_game,
+        None => new_game(options, used_words)?,
+    };
+    used_words.insert(game.secret_word());
+    game.ignore_accents = options.ignore_accents;
+    let start_time = Instant::now();
+
+    while !game.is_won() && !game.is_lost() {
+        print_gallows(game.num_guesses - game.num_guess_left, game.num_guesses);
+        println!("Category: {}", category);
+        print_word_so_far(
+            &game.revealed_indexes,
+            &game.secret_word_chars,
+            options.placeholder,
+            options.color,
+        );
+        print_guessed_so_far(&game.guessed_so_far, &game.wrong_guesses, options.color);
+        print_num_guess_left(game.num_guess_left);
+        if let Some(time_limit) = options.time_limit {
+            let time_left = time_limit.checked_sub(start_time.elapsed());
+            println!(
+                "You have {} seconds left",
+                time_left.unwrap_or(Duration::from_secs(0)).as_secs()
+            );
+        }
+
+        let guess = match get_guess(options.input) {
+            Some(guess) => guess,
+            None => {
+                println!("Quitting. The word was: {}", game.secret_word());
+                return None;
+            }
+        };
+        if let Some(time_limit) = options.time_limit {
+            if start_time.elapsed() > time_limit {
+                println!("Time's up!");
+                game.forfeit();
+                break;
+            }
+        }
+        match guess {
+            Guess::Letter(c) => match game.guess(c) {
+                GuessResult::Correct => {}
+                GuessResult::Incorrect => {
+                    ring_bell(options);
+                    println!(
+                        "{}",
+                        color::red("Sorry, that letter is not in the word", options.color)
+                    );
+                }
+                GuessResult::AlreadyGuessed => println!("You already guessed that letter"),
+            },
+            Guess::Word(letters) if options.multi_letter => {
+                for (c, result) in game.guess_letters(&letters) {
+                    match result {
+                        GuessResult::Correct => println!("{}: in the word", c),
+                        GuessResult::Incorrect => {
+                            ring_bell(options);
+                            println!(
+                                "{}",
+                                color::red(&format!("{}: not in the word", c), options.color)
+                            );
+                        }
+                        GuessResult::AlreadyGuessed => println!("{}: already guessed", c),
+                    }
+                }
+            }
+            Guess::Word(word) => {
+                if game.guess_word(&word) == GuessResult::Incorrect {
+                    ring_bell(options);
+                    println!(
+                        "{}",
+                        color::red("Sorry, that is not the word", options.color)
+                    );
+                }
+            }
+            Guess::Hint => {
+                if game.num_hints_used >= options.max_hints {
+                    println!("No hints remaining");
+                } else {
+                    match game.hint() {
+                        HintResult::Revealed(index) => println!(
+                            "Hint: letter {} is '{}'",
+                            index + 1,
+                            game.secret_word_chars[index]
+                        ),
+                        HintResult::TooFewHidden => {
+                            println!("Sorry, there aren't enough hidden letters left for a hint")
+                        }
+                        HintResult::LastGuess => {
+                            println!("Sorry, a hint costs a guess and you only have one left")
+                        }
+                    }
+                }
+            }
+            Guess::Stats => match game.describe() {
+                Some((num_vowels, num_distinct_letters)) => println!(
+                    "The word has {} vowels and {} distinct letters",
+                    num_vowels, num_distinct_letters
+                ),
+                None => println!("You've already seen the stats for this word"),
+            },
+            Guess::Undo => {
+                if game.undo() {
+                    println!("Your last guess was undone");
+                } else {
+                    println!("There's no guess to undo");
+                }
+            }
+            Guess::Save => match save_game(SAVE_PATH, &category, &game) {
+                Ok(()) => println!(
+                    "Game saved to {}. Run with --resume to pick it back up.",
+                    SAVE_PATH
+                ),
+                Err(err) => println!("Unable to save the game: {}", err),
+            },
+        }
+
+        println!();
+    }
+
+    print_result(&game, options.color);
+    print_word_difficulty(&game.secret_word_chars);
+    Some(game.num_guess_left)
+}
+
+struct LeaderboardEntry {
+    name: String,
+    score: u32,
+}
+
+// Reads the leaderboard at path, which holds one "score name" entry per line. Lines that don't
+// parse are skipped, and a missing file is just an empty leaderboard.
+fn load_leaderboard(path: &str) -> Vec<LeaderboardEntry> {
+    let contents = fs::read_to_string(path).unwrap_or_default();
+    let mut entries: Vec<LeaderboardEntry> = Vec::new();
+    for line in contents.lines() {
+        let mut parts = line.trim().splitn(2, ' ');
+        let score = parts.next().and_then(|score| score.parse::<u32>().ok());
+        let name = parts.next().map(|name| name.trim()).unwrap_or("");
+        if let Some(score) = score {
+            if !name.is_empty() {
+                entries.push(LeaderboardEntry {
+                    name: name.to_string(),
+                    score,
+                });
+            }
+        }
+    }
+    sort_leaderboard(&mut entries);
+    entries
+}
+
+// Orders the leaderboard from highest score to lowest.
+fn sort_leaderboard(entries: &mut [LeaderboardEntry]) {
+    entries.sort_by_key(|entry| Reverse(entry.score));
+}
+
+fn save_leaderboard(path: &str, entries: &[LeaderboardEntry]) -> io::Result<()> {
+    let mut contents = String::new();
+    for entry in entries {
+        contents.push_str(&format!("{} {}\n", entry.score, entry.name));
+    }
+    fs::write(path, contents)
+}
+
+fn print_leaderboard(entries: &[LeaderboardEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+    println!("High scores:");
+    for (i, entry) in entries.iter().take(LEADERBOARD_SIZE).enumerate() {
+        println!("  {}. {} - {}", i + 1, entry.name, entry.score);
+    }
+}
+
+// Asks for the player's name and adds their score to the leaderboard file.
+fn record_high_score(source: input::Source, score: u32) {
+    print!("Enter your name for the leaderboard: ");
+    io::stdout().flush().expect("Error flushing stdout.");
+
+    let mut name = String::new();
+    let num_bytes = input::read_line(source, &mut name);
+    let name = name.trim();
+    if num_bytes == 0 || name.is_empty() {
+        println!();
+        return;
+    }
 
-    while 0 < num_guess_left {
-        print_word_so_far(&revealed_indexes, &secret_word_chars);
-        print_guessed_so_far(&guessed_so_far);
-        print_num_guess_left(num_guess_left);
+    let mut entries = load_leaderboard(LEADERBOARD_PATH);
+    entries.push(LeaderboardEntry {
+        name: name.to_string(),
+        score,
+    });
+    sort_leaderboard(&mut entries);
+    if let Err(err) = save_leaderboard(LEADERBOARD_PATH, &entries) {
+        println!("Unable to save the leaderboard: {}", err);
+    }
+    print_leaderboard(&entries);
+}
 
-        let c: char = get_guess();
-        num_guess_left =
-            process_user_input(&mut revealed_indexes, &secret_word_chars, c, num_guess_left);
-        guessed_so_far.push(c);
+fn main() {
+    let options = parse_args();
+    println!("Welcome to CS110L Hangman!");
+    print_leaderboard(&load_leaderboard(LEADERBOARD_PATH));
 
+    let mut session = Session::new();
+    let mut saved_game = if options.resume {
+        load_game(SAVE_PATH)
+    } else {
+        None
+    };
+    while let Some(num_guess_left) = play_game(&options, saved_game.take(), &mut session.used_words)
+    {
+        session.record_game(num_guess_left);
+        session.print_running_total();
+        // There's only one daily word, so playing again would just repeat it.
+        if options.daily {
+            println!("Come back tomorrow for a new daily word!");
+            break;
+        }
+        if !ask_play_again(options.input) {
+            break;
+        }
         println!();
     }
+    session.print_summary();
+    record_high_score(options.input, session.score);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_correct_guess_reveals_all_matches() {
+        let mut game = Game::new("banana", 5);
+        assert_eq!(game.guess('a'), GuessResult::Correct);
+        assert_eq!(
+            game.revealed_indexes,
+            vec![false, true, false, true, false, true]
+        );
+        assert_eq!(game.num_guess_left, 5);
+    }
+
+    #[test]
+    fn test_incorrect_guess_decrements() {
+        let mut game = Game::new("banana", 5);
+        assert_eq!(game.guess('z'), GuessResult::Incorrect);
+        assert_eq!(game.num_guess_left, 4);
+        assert!(game.revealed_indexes.iter().all(|&revealed| !revealed));
+    }
+
+    #[test]
+    fn test_wrong_guesses() {
+        let mut game = Game::new("banana", 5);
+        game.guess('z');
+        game.guess('a');
+        game.guess('x');
+        game.guess('z');
+        assert_eq!(game.guessed_so_far, vec!['z', 'a', 'x']);
+        assert_eq!(game.wrong_guesses, vec!['z', 'x']);
+    }
+
+    #[test]
+    fn test_sorted_letters() {
+        assert_eq!(sorted_letters(&['t', 'a', 'Q', 'q', 'a']), "aqt");
+        assert_eq!(sorted_letters(&[]), "");
+    }
+
+    #[test]
+    fn test_repeated_guess_does_not_decrement_twice() {
+        let mut game = Game::new("banana", 5);
+        assert_eq!(game.guess('z'), GuessResult::Incorrect);
+        assert_eq!(game.guess('z'), GuessResult::AlreadyGuessed);
+        assert_eq!(game.guess('Z'), GuessResult::AlreadyGuessed);
+        assert_eq!(game.num_guess_left, 4);
+        assert_eq!(game.guessed_so_far, vec!['z']);
+    }
+
+    #[test]
+    fn test_winning_guess() {
+        let mut game = Game::new("banana", 5);
+        game.guess('b');
+        game.guess('a');
+        assert!(!game.is_won());
+        assert_eq!(game.guess('n'), GuessResult::Correct);
+        assert!(game.revealed_indexes.iter().all(|&revealed| revealed));
+        assert!(game.is_won());
+        assert!(!game.is_lost());
+    }
+
+    #[test]
+    fn test_accented_letters() {
+        let mut game = Game::new("café", 5);
+        assert_eq!(game.revealed_indexes.len(), 4);
+        assert_eq!(game.guess('é'), GuessResult::Correct);
+        assert_eq!(game.revealed_indexes, vec![false, false, false, true]);
+
+        let mut game = Game::new("Señor", 5);
+        assert_eq!(game.guess('Ñ'), GuessResult::Correct);
+        assert_eq!(
+            game.revealed_indexes,
+            vec![false, false, true, false, false]
+        );
+        assert_eq!(game.guess('n'), GuessResult::Incorrect);
+        assert_eq!(game.guess_word("señor"), GuessResult::Correct);
+        assert!(game.is_won());
+    }
+
+    #[test]
+    fn test_ignore_accents() {
+        let mut game = Game::new("crème", 5);
+        assert_eq!(game.guess('e'), GuessResult::Correct);
+        assert_eq!(
+            game.revealed_indexes,
+            vec![false, false, false, false, true]
+        );
+
+        game.ignore_accents = true;
+        assert_eq!(game.guess('E'), GuessResult::AlreadyGuessed);
+        assert_eq!(game.guess('é'), GuessResult::AlreadyGuessed);
+        assert_eq!(game.guess('c'), GuessResult::Correct);
+        assert_eq!(game.guess_word("creme"), GuessResult::Correct);
+        assert!(game.is_won());
+        assert_eq!(game.secret_word(), "crème");
+    }
 
-    print_result(num_guess_left, &secret_word);
+    #[test]
+    fn test_pick_a_random_word_avoids_used_words() {
+        let words = parse_words("cat\ndog");
+        let mut used_words: HashSet<String> = HashSet::new();
+        used_words.insert("cat".to_string());
+        assert_eq!(
+            pick_a_random_word(&words, Difficulty::Easy, &mut used_words),
+            "dog"
+        );
+
+        // Once every word has been used, they become available again.
+        used_words.insert("dog".to_string());
+        pick_a_random_word(&words, Difficulty::Easy, &mut used_words);
+        assert!(used_words.is_empty());
+    }
+
+    #[test]
+    fn test_parse_words_skips_blank_lines() {
+        let words = parse_words("cat\n\n  \ndog\n");
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "cat");
+        assert_eq!(words[1].text, "dog");
+        assert_eq!(parse_words("cat").len(), 1);
+        assert!(parse_words("").is_empty());
+        assert!(parse_words("\n \n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_word_line_weights() {
+        let word = parse_word_line("apple");
+        assert_eq!((word.text.as_str(), word.weight), ("apple", 1));
+        let word = parse_word_line("apple 3");
+        assert_eq!((word.text.as_str(), word.weight), ("apple", 3));
+        let word = parse_word_line("new york");
+        assert_eq!((word.text.as_str(), word.weight), ("new york", 1));
+        let word = parse_word_line("new york 2");
+        assert_eq!((word.text.as_str(), word.weight), ("new york", 2));
+        let word = parse_word_line("apple 3x");
+        assert_eq!((word.text.as_str(), word.weight), ("apple", 1));
+        let word = parse_word_line("apple -1");
+        assert_eq!((word.text.as_str(), word.weight), ("apple", 1));
+    }
+
+    #[test]
+    fn test_phrase() {
+        let mut game = Game::new("new york", 5);
+        assert_eq!(
+            game.revealed_indexes,
+            vec![false, false, false, true, false, false, false, false]
+        );
+        game.guess('n');
+        game.guess('e');
+        game.guess('w');
+        game.guess('y');
+        game.guess('o');
+        assert!(!game.is_won());
+        assert_eq!(game.guess('r'), GuessResult::Correct);
+        game.guess('k');
+        assert!(game.is_won());
+        assert_eq!(game.num_guess_left, 5);
+
+        let mut game = Game::new("new york", 5);
+        assert_eq!(game.guess_word("New York"), GuessResult::Correct);
+        assert!(game.is_won());
+        assert!(is_word_or_phrase(&['a', ' ', 'b']));
+        assert!(!is_word_or_phrase(&[' ']));
+    }
+
+    #[test]
+    fn test_describe_word() {
+        let chars: Vec<char> = "banana".chars().collect();
+        assert_eq!(describe_word(&chars), (3, 3));
+        let chars: Vec<char> = "New York".chars().collect();
+        assert_eq!(describe_word(&chars), (2, 7));
+
+        let mut game = Game::new("café", 5);
+        assert_eq!(game.describe(), Some((2, 4)));
+        assert_eq!(game.describe(), None);
+        assert_eq!(game.num_guess_left, 5);
+    }
+
+    #[test]
+    fn test_word_difficulty() {
+        let chars: Vec<char> = "banana".chars().collect();
+        assert_eq!(word_difficulty(&chars), 12);
+        // Case doesn't matter and the space isn't a letter, so this has 7 letters, all distinct.
+        let chars: Vec<char> = "New York".chars().collect();
+        assert_eq!(word_difficulty(&chars), 21);
+    }
+
+    #[test]
+    fn test_guess_letters() {
+        let mut game = Game::new("banana", 5);
+        game.guess('a');
+        assert_eq!(
+            game.guess_letters("bxa"),
+            vec![
+                ('b', GuessResult::Correct),
+                ('x', GuessResult::Incorrect),
+                ('a', GuessResult::AlreadyGuessed),
+            ]
+        );
+        assert_eq!(game.num_guess_left, 4);
+
+        // Letters after the one that ends the game aren't guessed.
+        assert_eq!(game.guess_letters("nz").len(), 1);
+        assert!(game.is_won());
+        assert_eq!(game.num_guess_left, 4);
+
+        let mut game = Game::new("cat", 2);
+        assert_eq!(game.guess_letters("xyz").len(), 2);
+        assert!(game.is_lost());
+    }
+
+    #[test]
+    fn test_undo() {
+        let mut game = Game::new("banana", 5);
+        assert!(!game.undo());
+
+        game.guess('a');
+        game.guess('z');
+        assert_eq!(game.guess_word("bandana"), GuessResult::Incorrect);
+        assert_eq!(game.num_guess_left, 3);
+
+        assert!(game.undo());
+        assert_eq!(game.num_guess_left, 4);
+        assert!(game.undo());
+        assert_eq!(game.num_guess_left, 5);
+        assert_eq!(game.guessed_so_far, vec!['a']);
+        assert!(game.wrong_guesses.is_empty());
+
+        assert!(game.undo());
+        assert!(game.revealed_indexes.iter().all(|&revealed| !revealed));
+        assert!(game.guessed_so_far.is_empty());
+        assert!(!game.undo());
+    }
+
+    #[test]
+    fn test_parse_placeholder() {
+        assert_eq!(parse_placeholder("_"), Some('_'));
+        assert_eq!(parse_placeholder("*"), Some('*'));
+        assert_eq!(parse_placeholder("x"), None);
+        assert_eq!(parse_placeholder(" "), None);
+        assert_eq!(parse_placeholder("__"), None);
+        assert_eq!(parse_placeholder(""), None);
+    }
+
+    #[test]
+    fn test_gallows_scales_to_guess_count() {
+        let full = "  +---+\n  |   |\n  O   |\n /|\\  |\n / \\  |\n      |\n=========";
+        let empty = "  +---+\n  |   |\n      |\n      |\n      |\n      |\n=========";
+        for &total in [4, 5, 8].iter() {
+            assert_eq!(draw_gallows(0, total), empty);
+            assert_eq!(draw_gallows(total, total), full);
+            assert!(num_body_parts(total - 1, total) < NUM_BODY_PARTS);
+            for used in 1..=total {
+                assert!(num_body_parts(used, total) >= num_body_parts(used - 1, total));
+            }
+        }
+        assert_eq!(num_body_parts(2, 4), 3);
+        assert_eq!(num_body_parts(4, 8), 3);
+    }
+
+    #[test]
+    fn test_win_streak() {
+        let mut session = Session::new();
+        session.record_game(3);
+        session.record_game(1);
+        assert_eq!((session.streak, session.best_streak), (2, 2));
+        session.record_game(0);
+        assert_eq!((session.streak, session.best_streak), (0, 2));
+        session.record_game(5);
+        assert_eq!((session.streak, session.best_streak), (1, 2));
+    }
+
+    #[test]
+    fn test_hint() {
+        let mut game = Game::new("banana", 2);
+        match game.hint() {
+            HintResult::Revealed(index) => assert!(game.revealed_indexes[index]),
+            result => panic!("expected a hint, got {:?}", result),
+        }
+        assert_eq!((game.num_guess_left, game.num_hints_used), (1, 1));
+
+        // The last guess can't be spent on a hint.
+        assert_eq!(game.hint(), HintResult::LastGuess);
+        assert_eq!((game.num_guess_left, game.num_hints_used), (1, 1));
+        assert_eq!(game.revealed_indexes.iter().filter(|&&r| r).count(), 1);
+
+        let mut game = Game::new("cat", 5);
+        game.guess('c');
+        game.guess('a');
+        assert_eq!(game.hint(), HintResult::TooFewHidden);
+        assert_eq!(game.num_guess_left, 5);
+    }
+
+    #[test]
+    fn test_losing_game() {
+        let mut game = Game::new("cat", 2);
+        game.guess('x');
+        assert!(!game.is_lost());
+        game.guess('y');
+        assert!(game.is_lost());
+        assert!(!game.is_won());
+    }
 }
@@ -1,4 +1,8 @@
+use std::collections::VecDeque;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::FromIterator;
+use std::ops::{Index, Range};
 use std::option::Option;
 
 pub struct LinkedList<T> {
@@ -13,10 +17,7 @@ struct Node<T> {
 
 impl<T> Node<T> {
     pub fn new(value: T, next: Option<Box<Node<T>>>) -> Node<T> {
-        Node {
-            value: value,
-            next: next,
-        }
+        Node { value, next }
     }
 }
 
@@ -50,32 +51,211 @@ impl<T> LinkedList<T> {
         self.head = Some(new_node);
         self.size += 1;
     }
+    /// Appends value to the end of the list. There's no tail pointer, so this walks the whole list
+    /// and takes O(n) time.
+    pub fn push_back(&mut self, value: T) {
+        let mut current: &mut Option<Box<Node<T>>> = &mut self.head;
+        while let Some(node) = current {
+            current = &mut node.next;
+        }
+        *current = Some(Box::new(Node::new(value, None)));
+        self.size += 1;
+    }
     pub fn pop_front(&mut self) -> Option<T> {
         let node: Box<Node<T>> = self.head.take()?;
         self.head = node.next;
         self.size -= 1;
         Some(node.value)
     }
+    /// Removes and returns the last element. Like push_back, this walks the whole list.
+    pub fn pop_back(&mut self) -> Option<T> {
+        let mut current: &mut Option<Box<Node<T>>> = &mut self.head;
+        while current.as_ref()?.next.is_some() {
+            current = &mut current.as_mut().unwrap().next;
+        }
+        let node: Box<Node<T>> = current.take()?;
+        self.size -= 1;
+        Some(node.value)
+    }
+    /// Returns a reference to the first element without removing it, in O(1) time.
+    pub fn peek_front(&self) -> Option<&T> {
+        self.head.as_ref().map(|node| &node.value)
+    }
+    /// Returns a reference to the last element without removing it. There's no tail pointer, so
+    /// this walks the whole list and takes O(n) time.
+    pub fn peek_back(&self) -> Option<&T> {
+        self.iter().last()
+    }
+    /// Returns a reference to the element at index, or None if index is out of range.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.iter().nth(index)
+    }
+    /// Returns a mutable reference to the element at index, or None if index is out of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.iter_mut().nth(index)
+    }
+    /// Inserts value so that it ends up at index, shifting the elements after it back by one.
+    /// Panics if index is greater than the size of the list, like Vec::insert.
+    pub fn insert(&mut self, index: usize, value: T) {
+        if index > self.size {
+            panic!("insertion index {} out of range for list of size {}", index, self.size);
+        }
+        let mut current: &mut Option<Box<Node<T>>> = &mut self.head;
+        for _ in 0..index {
+            current = &mut current.as_mut().unwrap().next;
+        }
+        *current = Some(Box::new(Node::new(value, current.take())));
+        self.size += 1;
+    }
+    /// Removes and returns the element at index, or returns None if index is out of range.
+    pub fn remove(&mut self, index: usize) -> Option<T> {
+        let mut current: &mut Option<Box<Node<T>>> = &mut self.head;
+        for _ in 0..index {
+            current = &mut current.as_mut()?.next;
+        }
+        let node: Box<Node<T>> = current.take()?;
+        *current = node.next;
+        self.size -= 1;
+        Some(node.value)
+    }
+    /// Reverses the order of the elements in place by relinking the nodes, in O(n) time.
+    pub fn reverse(&mut self) {
+        let mut reversed: Option<Box<Node<T>>> = None;
+        let mut current: Option<Box<Node<T>>> = self.head.take();
+        while let Some(mut node) = current {
+            current = node.next.take();
+            node.next = reversed;
+            reversed = Some(node);
+        }
+        self.head = reversed;
+    }
+    /// Returns a new list holding f applied to each element, in the same order. The original
+    /// list is left unchanged.
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> LinkedList<U> {
+        self.iter().map(f).collect()
+    }
+    /// Removes every element for which f returns false, keeping the rest in order.
+    pub fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        let mut current: &mut Option<Box<Node<T>>> = &mut self.head;
+        while current.is_some() {
+            if f(&current.as_ref().unwrap().value) {
+                current = &mut current.as_mut().unwrap().next;
+            } else {
+                let removed: Box<Node<T>> = current.take().unwrap();
+                *current = removed.next;
+                self.size -= 1;
+            }
+        }
+    }
+    /// Removes the elements in range and returns them in order. Panics if the range is out of
+    /// bounds, like Vec::drain.
+    pub fn drain(&mut self, range: Range<usize>) -> Vec<T> {
+        if range.start > range.end {
+            panic!("drain range starts at {} but ends at {}", range.start, range.end);
+        }
+        if range.end > self.size {
+            panic!("drain range end {} out of range for list of size {}", range.end, self.size);
+        }
+        let mut current: &mut Option<Box<Node<T>>> = &mut self.head;
+        for _ in 0..range.start {
+            current = &mut current.as_mut().unwrap().next;
+        }
+        let mut drained: Vec<T> = Vec::new();
+        for _ in range.clone() {
+            let node: Box<Node<T>> = current.take().unwrap();
+            *current = node.next;
+            drained.push(node.value);
+        }
+        self.size -= drained.len();
+        drained
+    }
+    /// Returns an iterator over references to the values, front to back.
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            next: self.head.as_deref(),
+            collected: None,
+        }
+    }
+    /// Returns an iterator over mutable references to the values, front to back.
+    pub fn iter_mut(&mut self) -> IterMut<'_, T> {
+        IterMut {
+            next: self.head.as_deref_mut(),
+        }
+    }
+}
+
+impl<T: PartialEq> LinkedList<T> {
+    /// Returns true if any element is equal to value.
+    pub fn contains(&self, value: &T) -> bool {
+        self.iter().any(|element| element == value)
+    }
+    /// Removes consecutive equal elements, like Vec::dedup. On a sorted list this leaves exactly
+    /// one node per distinct value.
+    pub fn dedup_sorted(&mut self) {
+        let mut current: &mut Option<Box<Node<T>>> = &mut self.head;
+        while let Some(node) = current {
+            while node.next.as_ref().is_some_and(|next| next.value == node.value) {
+                let duplicate: Box<Node<T>> = node.next.take().unwrap();
+                node.next = duplicate.next;
+                self.size -= 1;
+            }
+            current = &mut node.next;
+        }
+    }
+}
+
+impl<T> Default for LinkedList<T> {
+    fn default() -> Self {
+        LinkedList::new()
+    }
+}
+
+impl<T> From<Vec<T>> for LinkedList<T> {
+    /// Builds a list with the vector's first element at the front. Pushing from the back of the
+    /// vector onto the front of the list keeps this O(n).
+    fn from(values: Vec<T>) -> Self {
+        let mut list: LinkedList<T> = LinkedList::new();
+        for value in values.into_iter().rev() {
+            list.push_front(value);
+        }
+        list
+    }
+}
+
+impl<T> FromIterator<T> for LinkedList<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        LinkedList::from(iter.into_iter().collect::<Vec<T>>())
+    }
+}
+
+impl<T> Index<usize> for LinkedList<T> {
+    type Output = T;
+    /// Returns a reference to the element at index. Each access walks the list from the head, so
+    /// it takes O(n) time; prefer iter() when visiting every element. Panics if index is out of
+    /// range.
+    fn index(&self, index: usize) -> &T {
+        match self.get(index) {
+            Some(value) => value,
+            None => panic!("index {} out of range for list of size {}", index, self.size),
+        }
+    }
 }
 
 impl<T: fmt::Display> fmt::Display for LinkedList<T> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let mut current: &Option<Box<Node<T>>> = &self.head;
         let mut result = String::new();
-        loop {
-            match current {
-                Some(node) => {
-                    result = format!("{} {}", result, node.value);
-                    current = &node.next;
-                }
-                None => break,
-            }
+        while let Some(node) = current {
+            result = format!("{} {}", result, node.value);
+            current = &node.next;
         }
         write!(f, "{}", result)
     }
 }
 
 impl<T> Drop for LinkedList<T> {
+    /// Frees the nodes one at a time. Otherwise dropping the head Box would drop the rest of the
+    /// list recursively, which can overflow the stack for a long list.
     fn drop(&mut self) {
         let mut current = self.head.take();
         while let Some(mut node) = current {
@@ -89,15 +269,10 @@ impl<T: Clone> Clone for LinkedList<T> {
         let mut cloned: LinkedList<T> = LinkedList::new();
         let mut head: &Option<Box<Node<T>>> = &self.head;
         let mut value_list: Vec<T> = Vec::new();
-        loop {
-            match head {
-                Some(node) => {
-                    let cloned_node = node.clone();
-                    value_list.push(cloned_node.value);
-                    head = &node.next;
-                }
-                None => break,
-            }
+        while let Some(node) = head {
+            let cloned_node = node.clone();
+            value_list.push(cloned_node.value);
+            head = &node.next;
         }
 
         for val in value_list.iter().rev() {
@@ -118,3 +293,464 @@ impl<T: PartialEq> PartialEq for LinkedList<T> {
         self.head == other.head
     }
 }
+
+impl<T: Eq> Eq for LinkedList<T> {}
+
+impl<T: Hash> Hash for LinkedList<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.size.hash(state);
+        let mut current: &Option<Box<Node<T>>> = &self.head;
+        while let Some(node) = current {
+            node.value.hash(state);
+            current = &node.next;
+        }
+    }
+}
+
+/// An iterator that moves the values out of a LinkedList, front to back.
+pub struct IntoIter<T>(LinkedList<T>);
+
+impl<T> Iterator for IntoIter<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+}
+
+impl<T> IntoIterator for LinkedList<T> {
+    type Item = T;
+    type IntoIter = IntoIter<T>;
+    fn into_iter(self) -> IntoIter<T> {
+        IntoIter(self)
+    }
+}
+
+/// An iterator over references to the values in a LinkedList, front to back.
+///
+/// The nodes only link forward, so there's no cheap way to step back from the end. The first call
+/// to next_back() walks the rest of the list once and collects references to the remaining values
+/// into a VecDeque, which takes O(n) time and memory; after that both ends are served from the
+/// deque in O(1). Iterating only forward never allocates.
+pub struct Iter<'a, T> {
+    next: Option<&'a Node<T>>,
+    collected: Option<VecDeque<&'a T>>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+    fn next(&mut self) -> Option<&'a T> {
+        if let Some(collected) = &mut self.collected {
+            return collected.pop_front();
+        }
+        let node: &'a Node<T> = self.next?;
+        self.next = node.next.as_deref();
+        Some(&node.value)
+    }
+}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<&'a T> {
+        if self.collected.is_none() {
+            let mut collected: VecDeque<&'a T> = VecDeque::new();
+            while let Some(node) = self.next {
+                collected.push_back(&node.value);
+                self.next = node.next.as_deref();
+            }
+            self.collected = Some(collected);
+        }
+        self.collected.as_mut().unwrap().pop_back()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a LinkedList<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+    fn into_iter(self) -> Iter<'a, T> {
+        self.iter()
+    }
+}
+
+/// An iterator over mutable references to the values in a LinkedList, front to back.
+///
+/// No unsafe code is needed: next() takes the iterator's only reference to the current node, then
+/// splits it into a reference to the value (handed out) and one to the next node (kept), so no two
+/// live references ever point at the same node.
+pub struct IterMut<'a, T> {
+    next: Option<&'a mut Node<T>>,
+}
+
+impl<'a, T> Iterator for IterMut<'a, T> {
+    type Item = &'a mut T;
+    fn next(&mut self) -> Option<&'a mut T> {
+        let node: &'a mut Node<T> = self.next.take()?;
+        self.next = node.next.as_deref_mut();
+        Some(&mut node.value)
+    }
+}
+
+impl<'a, T> IntoIterator for &'a mut LinkedList<T> {
+    type Item = &'a mut T;
+    type IntoIter = IterMut<'a, T>;
+    fn into_iter(self) -> IterMut<'a, T> {
+        self.iter_mut()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+
+    fn list_of(values: &[i32]) -> LinkedList<i32> {
+        let mut list: LinkedList<i32> = LinkedList::new();
+        for value in values.iter().rev() {
+            list.push_front(*value);
+        }
+        list
+    }
+
+    fn hash_of(list: &LinkedList<i32>) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        list.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_hash_equal_lists() {
+        let list = list_of(&[1, 2, 3]);
+        assert_eq!(hash_of(&list), hash_of(&list_of(&[1, 2, 3])));
+        assert_eq!(hash_of(&list), hash_of(&list.clone()));
+        assert_ne!(hash_of(&list), hash_of(&list_of(&[3, 2, 1])));
+    }
+
+    #[test]
+    fn test_hash_set_dedups_equal_lists() {
+        let mut set: HashSet<LinkedList<i32>> = HashSet::new();
+        set.insert(list_of(&[1, 2, 3]));
+        set.insert(list_of(&[1, 2, 3]));
+        set.insert(list_of(&[1, 2]));
+        set.insert(LinkedList::new());
+        assert_eq!(set.len(), 3);
+        assert!(set.contains(&list_of(&[1, 2])));
+    }
+
+    #[test]
+    fn test_dedup_sorted() {
+        let mut list = list_of(&[1, 1, 2, 3, 3, 3, 4, 5, 5]);
+        list.dedup_sorted();
+        assert_eq!(list.get_size(), 5);
+        assert!(list == list_of(&[1, 2, 3, 4, 5]));
+    }
+
+    #[test]
+    fn test_dedup_sorted_no_duplicates() {
+        let mut list = list_of(&[1, 2, 3]);
+        list.dedup_sorted();
+        assert_eq!(list.get_size(), 3);
+        assert!(list == list_of(&[1, 2, 3]));
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        empty.dedup_sorted();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_retain() {
+        let mut list = list_of(&[1, 2, 3, 4, 5, 6]);
+        list.retain(|value| value % 2 == 0);
+        assert_eq!(list.get_size(), 3);
+        assert!(list == list_of(&[2, 4, 6]));
+    }
+
+    #[test]
+    fn test_retain_all_and_none() {
+        let mut all = list_of(&[1, 2, 3]);
+        all.retain(|_| true);
+        assert_eq!(all.get_size(), 3);
+        assert!(all == list_of(&[1, 2, 3]));
+
+        let mut none = list_of(&[1, 2, 3]);
+        none.retain(|_| false);
+        assert_eq!(none.get_size(), 0);
+        assert!(none.is_empty());
+        assert!(none == LinkedList::new());
+    }
+
+    #[test]
+    fn test_drain_middle() {
+        let mut list = list_of(&[1, 2, 3, 4, 5]);
+        assert_eq!(list.drain(1..4), vec![2, 3, 4]);
+        assert_eq!(list.get_size(), 2);
+        assert!(list == list_of(&[1, 5]));
+    }
+
+    #[test]
+    fn test_drain_head() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert_eq!(list.drain(0..2), vec![1, 2]);
+        assert_eq!(list.get_size(), 1);
+        assert!(list == list_of(&[3]));
+    }
+
+    #[test]
+    fn test_drain_empty_range() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert_eq!(list.drain(2..2), Vec::<i32>::new());
+        assert_eq!(list.get_size(), 3);
+        assert!(list == list_of(&[1, 2, 3]));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_drain_out_of_range() {
+        let mut list = list_of(&[1, 2, 3]);
+        list.drain(1..4);
+    }
+
+    #[test]
+    fn test_into_iter() {
+        let list = list_of(&[1, 2, 3]);
+        let values: Vec<i32> = list.into_iter().collect();
+        assert_eq!(values, vec![1, 2, 3]);
+
+        let mut values: Vec<i32> = Vec::new();
+        for value in list_of(&[4, 5]) {
+            values.push(value);
+        }
+        assert_eq!(values, vec![4, 5]);
+    }
+
+    #[test]
+    fn test_iter_twice() {
+        let list = list_of(&[1, 2, 3]);
+        let mut values: Vec<i32> = Vec::new();
+        for value in &list {
+            values.push(*value);
+        }
+        assert_eq!(values, vec![1, 2, 3]);
+        assert_eq!(list.iter().sum::<i32>(), 6);
+        assert_eq!(list.get_size(), 3);
+        assert!(list == list_of(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn test_iter_mut() {
+        let mut list = list_of(&[1, 2, 3]);
+        for value in &mut list {
+            *value += 10;
+        }
+        let values: Vec<&i32> = list.iter().collect();
+        assert_eq!(values, vec![&11, &12, &13]);
+
+        let mut empty: LinkedList<i32> = LinkedList::new();
+        assert!(empty.iter_mut().next().is_none());
+    }
+
+    #[test]
+    fn test_drop_long_list() {
+        let mut list: LinkedList<i32> = LinkedList::new();
+        for i in 0..100000 {
+            list.push_front(i);
+        }
+        assert_eq!(list.get_size(), 100000);
+        drop(list);
+    }
+
+    #[test]
+    fn test_push_back() {
+        let mut list: LinkedList<i32> = LinkedList::new();
+        for i in 1..=3 {
+            list.push_back(i);
+        }
+        assert_eq!(list.get_size(), 3);
+        assert!(list == list_of(&[1, 2, 3]));
+        assert_eq!(list.to_string(), " 1 2 3");
+
+        let mut front: LinkedList<i32> = LinkedList::new();
+        for i in (1..=3).rev() {
+            front.push_front(i);
+        }
+        assert!(list == front);
+    }
+
+    #[test]
+    fn test_pop_back() {
+        let mut list = list_of(&[1, 2, 3]);
+        assert_eq!(list.pop_back(), Some(3));
+        assert_eq!(list.get_size(), 2);
+        assert!(list == list_of(&[1, 2]));
+        list.push_back(4);
+        assert!(list == list_of(&[1, 2, 4]));
+        assert_eq!(list.pop_back(), Some(4));
+        assert_eq!(list.pop_back(), Some(2));
+        assert_eq!(list.pop_back(), Some(1));
+        assert!(list.is_empty());
+    }
+
+    #[test]
+    fn test_pop_back_empty() {
+        let mut list: LinkedList<i32> = LinkedList::new();
+        assert_eq!(list.pop_back(), None);
+        assert_eq!(list.get_size(), 0);
+    }
+
+    #[test]
+    fn test_get() {
+        let list = list_of(&[1, 2, 3]);
+        assert_eq!(list.get(0), Some(&1));
+        assert_eq!(list.get(1), Some(&2));
+        assert_eq!(list.get(2), Some(&3));
+        assert_eq!(list.get(3), None);
+        assert_eq!(LinkedList::<i32>::new().get(0), None);
+    }
+
+    #[test]
+    fn test_get_mut() {
+        let mut list = list_of(&[1, 2, 3]);
+        *list.get_mut(1).unwrap() = 20;
+        assert_eq!(list.get(1), Some(&20));
+        assert!(list == list_of(&[1, 20, 3]));
+        assert_eq!(list.get_mut(3), None);
+    }
+
+    #[test]
+    fn test_insert() {
+        let mut list = list_of(&[2, 4]);
+        list.insert(0, 1);
+        assert!(list == list_of(&[1, 2, 4]));
+        list.insert(2, 3);
+        assert!(list == list_of(&[1, 2, 3, 4]));
+        list.insert(4, 5);
+        assert!(list == list_of(&[1, 2, 3, 4, 5]));
+        assert_eq!(list.get_size(), 5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_range() {
+        let mut list = list_of(&[1, 2]);
+        list.insert(3, 3);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut list = list_of(&[1, 2, 3, 4]);
+        assert_eq!(list.remove(0), Some(1));
+        assert!(list == list_of(&[2, 3, 4]));
+        assert_eq!(list.remove(1), Some(3));
+        assert!(list == list_of(&[2, 4]));
+        assert_eq!(list.remove(1), Some(4));
+        assert!(list == list_of(&[2]));
+        assert_eq!(list.get_size(), 1);
+    }
+
+    #[test]
+    fn test_remove_out_of_range() {
+        let mut list = list_of(&[1, 2]);
+        assert_eq!(list.remove(2), None);
+        assert_eq!(list.remove(5), None);
+        assert!(list == list_of(&[1, 2]));
+        assert_eq!(LinkedList::<i32>::new().remove(0), None);
+    }
+
+    #[test]
+    fn test_reverse() {
+        let mut list = list_of(&[1, 2, 3]);
+        list.reverse();
+        assert_eq!(list.to_string(), " 3 2 1");
+        assert_eq!(list.get_size(), 3);
+
+        let mut single = list_of(&[1]);
+        single.reverse();
+        assert!(single == list_of(&[1]));
+
+        let mut empty = list_of(&[]);
+        empty.reverse();
+        assert!(empty.is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        let list = list_of(&[1, 2, 3]);
+        assert!(list.contains(&2));
+        assert!(!list.contains(&4));
+        assert!(!list_of(&[]).contains(&1));
+    }
+
+    #[test]
+    fn test_from_vec() {
+        let mut expected: LinkedList<i32> = LinkedList::new();
+        expected.push_back(1);
+        expected.push_back(2);
+        expected.push_back(3);
+        let list = LinkedList::from(vec![1, 2, 3]);
+        assert!(list == expected);
+        assert_eq!(list.get_size(), 3);
+        assert!(LinkedList::<i32>::from(Vec::new()).is_empty());
+    }
+
+    #[test]
+    fn test_collect() {
+        let list: LinkedList<i32> = (1..=5).collect();
+        assert!(list == list_of(&[1, 2, 3, 4, 5]));
+        assert_eq!(list.get_size(), 5);
+    }
+
+    #[test]
+    fn test_index() {
+        let list = list_of(&[1, 2, 3]);
+        assert_eq!(list[0], 1);
+        assert_eq!(list[2], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "index 3 out of range for list of size 3")]
+    fn test_index_out_of_range() {
+        let list = list_of(&[1, 2, 3]);
+        let _ = list[3];
+    }
+
+    #[test]
+    fn test_peek() {
+        let empty = list_of(&[]);
+        assert_eq!(empty.peek_front(), None);
+        assert_eq!(empty.peek_back(), None);
+
+        let list = list_of(&[1, 2, 3]);
+        assert_eq!(list.peek_front(), Some(&1));
+        assert_eq!(list.peek_back(), Some(&3));
+        assert_eq!(list.get_size(), 3);
+    }
+
+    #[test]
+    fn test_iter_rev() {
+        let list = list_of(&[1, 2, 3, 4]);
+        let mut forward: Vec<&i32> = list.iter().collect();
+        forward.reverse();
+        let backward: Vec<&i32> = list.iter().rev().collect();
+        assert_eq!(backward, forward);
+        assert_eq!(list_of(&[]).iter().next_back(), None);
+    }
+
+    #[test]
+    fn test_iter_both_ends() {
+        let list = list_of(&[1, 2, 3, 4]);
+        let mut iter = list.iter();
+        assert_eq!(iter.next(), Some(&1));
+        assert_eq!(iter.next_back(), Some(&4));
+        assert_eq!(iter.next(), Some(&2));
+        assert_eq!(iter.next_back(), Some(&3));
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+    }
+
+    #[test]
+    fn test_map() {
+        let list = list_of(&[1, 2, 3]);
+        let strings: LinkedList<String> = list.map(|value| format!("#{}", value));
+        assert_eq!(strings.to_string(), " #1 #2 #3");
+        assert_eq!(strings.get_size(), 3);
+        assert!(list == list_of(&[1, 2, 3]));
+    }
+}
@@ -1,6 +1,7 @@
 use linked_list::LinkedList;
 pub mod linked_list;
 
+#[allow(clippy::to_string_in_format_args)]
 fn main() {
     let mut list: LinkedList<char> = LinkedList::new();
     assert!(list.is_empty());
@@ -15,8 +16,7 @@ fn main() {
     println!("size: {}", list.get_size());
     println!("{}", list.to_string()); // ToString impl for anything impl Display
 
-    // If you implement iterator trait:
-    //for val in &list {
-    //    println!("{}", val);
-    //}
+    for val in &list {
+        println!("{}", val);
+    }
 }